@@ -0,0 +1,293 @@
+/* 基于数组实现的双向队列
+    与基于环形数组的队列类似，只是队首也可以入队和出队：队首入队时front向前移动一位，越过数组头部则回到数组尾部。
+    数组已满时扩容为原来的2倍，并将元素按顺序重新排列到新数组的头部。 */
+
+use std::fmt;
+use std::iter::FromIterator;
+
+use crate::Deque;
+
+// 基于环形数组实现的双向队列
+pub struct ArrayDeque<T> {
+    elems: Vec<Option<T>>, // 用于存储元素的数组，长度即为容量
+    front: usize,          // 队首指针，指向队首元素
+    que_size: usize,       // 双向队列长度
+}
+
+impl<T> ArrayDeque<T> {
+    // 构造函数
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    // 指定初始容量的构造函数
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            elems: (0..capacity).map(|_| None).collect(),
+            front: 0,
+            que_size: 0,
+        }
+    }
+
+    // 获取双向队列的容量
+    pub fn capacity(&self) -> usize {
+        self.elems.len()
+    }
+
+    // 计算环形数组索引：从队首向后偏移offset个位置
+    fn index(&self, offset: usize) -> usize {
+        (self.front + offset) % self.capacity()
+    }
+
+    // 扩容为原来的2倍，元素重新排列到新数组头部
+    fn grow(&mut self) {
+        let new_capacity = (self.capacity() * 2).max(4);
+        let mut elems: Vec<Option<T>> = Vec::with_capacity(new_capacity);
+        for i in 0..self.que_size {
+            let j = self.index(i);
+            elems.push(self.elems[j].take());
+        }
+        elems.resize_with(new_capacity, || None);
+        self.elems = elems;
+        self.front = 0;
+    }
+
+    // 访问并修改从队首数起的第index个元素
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.que_size {
+            return None;
+        }
+        let i = self.index(index);
+        self.elems[i].as_mut()
+    }
+
+    // 清空双向队列，保留容量
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.front = 0;
+    }
+
+    // 返回数组
+    pub fn to_array(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        Deque::iter(self).cloned().collect()
+    }
+}
+
+impl<T> Deque<T> for ArrayDeque<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+
+    fn size(&self) -> usize {
+        self.que_size
+    }
+
+    fn push_front(&mut self, elem: T) {
+        if self.que_size == self.capacity() {
+            self.grow();
+        }
+        // 队首指针向左移动一位，通过取余操作实现front越过数组头部后回到尾部
+        self.front = self.index(self.capacity() - 1);
+        self.elems[self.front] = Some(elem);
+        self.que_size += 1;
+    }
+
+    fn push_back(&mut self, elem: T) {
+        if self.que_size == self.capacity() {
+            self.grow();
+        }
+        // 计算队尾指针，指向队尾索引+1
+        let rear = self.index(self.que_size);
+        self.elems[rear] = Some(elem);
+        self.que_size += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.que_size == 0 {
+            return None;
+        }
+        let elem = self.elems[self.front].take();
+        // 队首指针向后移动一位
+        self.front = self.index(1);
+        self.que_size -= 1;
+        elem
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        if self.que_size == 0 {
+            return None;
+        }
+        let rear = self.index(self.que_size - 1);
+        self.que_size -= 1;
+        self.elems[rear].take()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    fn back(&self) -> Option<&T> {
+        self.que_size.checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.que_size {
+            return None;
+        }
+        self.elems[self.index(index)].as_ref()
+    }
+
+    fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.que_size, "rotate_left: n超过了双向队列长度");
+        if n == 0 {
+            return;
+        }
+        // 每次移动较短的一侧
+        if n > self.que_size - n {
+            return self.rotate_right(self.que_size - n);
+        }
+        if self.que_size == self.capacity() {
+            // 数组已满时，只需移动队首指针
+            self.front = self.index(n);
+            return;
+        }
+        for _ in 0..n {
+            let i = self.front;
+            let rear = self.index(self.que_size);
+            self.elems.swap(i, rear);
+            self.front = self.index(1);
+        }
+    }
+
+    fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.que_size, "rotate_right: n超过了双向队列长度");
+        if n == 0 {
+            return;
+        }
+        if n > self.que_size - n {
+            return self.rotate_left(self.que_size - n);
+        }
+        if self.que_size == self.capacity() {
+            self.front = self.index(self.capacity() - n);
+            return;
+        }
+        for _ in 0..n {
+            let last = self.index(self.que_size - 1);
+            self.front = self.index(self.capacity() - 1);
+            self.elems.swap(last, self.front);
+        }
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            deque: self,
+            head: 0,
+            tail: self.que_size,
+        }
+    }
+}
+
+impl<T> Default for ArrayDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(Deque::iter(self)).finish()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for ArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+/* 双向迭代器，[head, tail)为尚未访问的偏移区间 */
+pub struct Iter<'a, T> {
+    deque: &'a ArrayDeque<T>,
+    head: usize,
+    tail: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.head == self.tail {
+            return None;
+        }
+        self.head += 1;
+        self.deque.get(self.head - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.tail - self.head, Some(self.tail - self.head))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.head == self.tail {
+            return None;
+        }
+        self.tail -= 1;
+        self.deque.get(self.tail)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a ArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Deque::iter(self)
+    }
+}
+
+/* 按值的双向迭代器 */
+pub struct IntoIter<T>(ArrayDeque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size(), Some(self.0.size()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for ArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
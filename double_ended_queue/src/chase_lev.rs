@@ -0,0 +1,259 @@
+/* Chase–Lev工作窃取双向队列
+    工作窃取调度中，每个工作线程拥有一个双向队列：
+        1.拥有者在队尾(bottom)入队和出队，像栈一样后进先出，最近产生的任务数据仍在缓存中；
+        2.其他线程(窃取者)从队首(top)窃取，拿走的是最早产生、通常也是最大的任务。
+    拥有者只在队列只剩一个元素时才需要与窃取者竞争，此时双方都通过对top做CAS来决定归属。
+    环形数组写满时拥有者把元素复制到容量翻倍的新数组；窃取者可能仍在读取旧数组，因此旧数组保留到队列被释放时才回收。 */
+
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicIsize, AtomicPtr, Ordering};
+use std::sync::{Arc, Mutex};
+
+/* 按缓存行对齐，避免top和bottom伪共享 */
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+/* 容量为2的幂的环形数组，下标通过按位与取余 */
+struct Buffer<T> {
+    elems: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> Buffer<T> {
+    fn alloc(capacity: usize) -> *mut Self {
+        debug_assert!(capacity.is_power_of_two());
+        Box::into_raw(Box::new(Self {
+            elems: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+        }))
+    }
+
+    fn capacity(&self) -> usize {
+        self.elems.len()
+    }
+
+    fn slot(&self, index: isize) -> *mut MaybeUninit<T> {
+        self.elems[index as usize & (self.capacity() - 1)].get()
+    }
+
+    // 按位读取槽位，不转移所有权；只有赢得竞争的一方才会把结果当作T使用
+    unsafe fn read(&self, index: isize) -> MaybeUninit<T> {
+        ptr::read(self.slot(index))
+    }
+
+    unsafe fn write(&self, index: isize, elem: MaybeUninit<T>) {
+        ptr::write(self.slot(index), elem)
+    }
+}
+
+/* 拥有者和窃取者共享的状态 */
+struct Inner<T> {
+    top: CachePadded<AtomicIsize>,    // 窃取端
+    bottom: CachePadded<AtomicIsize>, // 拥有者端
+    buffer: AtomicPtr<Buffer<T>>,
+    retired: Mutex<Vec<*mut Buffer<T>>>, // 扩容后被替换下来的旧数组
+}
+
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let top = *self.top.0.get_mut();
+        let bottom = *self.bottom.0.get_mut();
+        let buffer = *self.buffer.get_mut();
+        // SAFETY: 已经没有其他句柄，[top, bottom)内的元素都归队列所有
+        unsafe {
+            for i in top..bottom {
+                (*buffer).read(i).assume_init_drop();
+            }
+            drop(Box::from_raw(buffer));
+            let retired = self.retired.get_mut().unwrap_or_else(|e| e.into_inner());
+            for old in retired.drain(..) {
+                drop(Box::from_raw(old));
+            }
+        }
+    }
+}
+
+/* 窃取的结果 */
+#[derive(Debug, PartialEq, Eq)]
+pub enum Steal<T> {
+    Empty,      // 队列为空
+    Success(T), // 窃取成功
+    Retry,      // 与其他线程竞争失败，可以重试
+}
+
+/* 队列的拥有者句柄，只能在一个线程中使用 */
+pub struct Worker<T> {
+    inner: Arc<Inner<T>>,
+    marker: PhantomData<Cell<()>>, // 拥有者句柄不能在线程间共享
+}
+
+/* 窃取者句柄，可以克隆并发送给其他线程 */
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Worker<T> {
+    // 构造函数
+    pub fn new() -> Self {
+        Self::with_capacity(32)
+    }
+
+    // 指定初始容量的构造函数，容量会被调整为2的幂
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        Self {
+            inner: Arc::new(Inner {
+                top: CachePadded(AtomicIsize::new(0)),
+                bottom: CachePadded(AtomicIsize::new(0)),
+                buffer: AtomicPtr::new(Buffer::alloc(capacity)),
+                retired: Mutex::new(Vec::new()),
+            }),
+            marker: PhantomData,
+        }
+    }
+
+    // 创建一个窃取者
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    // 队列长度(并发窃取时只是一个瞬时的结果)
+    pub fn size(&self) -> usize {
+        let bottom = self.inner.bottom.0.load(Ordering::Relaxed);
+        let top = self.inner.top.0.load(Ordering::Relaxed);
+        (bottom - top).max(0) as usize
+    }
+
+    // 判断队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    // 扩容为原来的2倍，只有拥有者会调用
+    fn grow(&self, top: isize, bottom: isize) -> *mut Buffer<T> {
+        let old = self.inner.buffer.load(Ordering::Relaxed);
+        // SAFETY: 只有拥有者会替换数组，old在队列释放之前一直有效
+        unsafe {
+            let new = Buffer::alloc((*old).capacity() * 2);
+            for i in top..bottom {
+                (*new).write(i, (*old).read(i));
+            }
+            self.inner.buffer.store(new, Ordering::Release);
+            self.inner
+                .retired
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(old);
+            new
+        }
+    }
+
+    /* 在队尾入队 */
+    pub fn push(&self, elem: T) {
+        let bottom = self.inner.bottom.0.load(Ordering::Relaxed);
+        let top = self.inner.top.0.load(Ordering::Acquire);
+        let mut buffer = self.inner.buffer.load(Ordering::Relaxed);
+        // SAFETY: 拥有者独占bottom处的槽位
+        unsafe {
+            if bottom - top >= (*buffer).capacity() as isize {
+                buffer = self.grow(top, bottom);
+            }
+            (*buffer).write(bottom, MaybeUninit::new(elem));
+        }
+        // 先写入元素，再发布新的bottom
+        fence(Ordering::Release);
+        self.inner.bottom.0.store(bottom + 1, Ordering::Relaxed);
+    }
+
+    /* 在队尾出队 */
+    pub fn pop(&self) -> Option<T> {
+        let bottom = self.inner.bottom.0.load(Ordering::Relaxed) - 1;
+        let buffer = self.inner.buffer.load(Ordering::Relaxed);
+        // 先预占bottom - 1，再读取top，确保与窃取者的读取顺序全局一致
+        self.inner.bottom.0.store(bottom, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let top = self.inner.top.0.load(Ordering::Relaxed);
+        if top > bottom {
+            // 队列为空，恢复bottom
+            self.inner.bottom.0.store(bottom + 1, Ordering::Relaxed);
+            return None;
+        }
+        // SAFETY: top <= bottom，槽位中的元素已经初始化
+        let elem = unsafe { (*buffer).read(bottom) };
+        if top < bottom {
+            // 至少还剩两个元素，窃取者不可能拿到bottom处的元素
+            // SAFETY: 元素归拥有者所有
+            return Some(unsafe { elem.assume_init() });
+        }
+        // 只剩最后一个元素，与窃取者竞争
+        let won = self
+            .inner
+            .top
+            .0
+            .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+            .is_ok();
+        self.inner.bottom.0.store(bottom + 1, Ordering::Relaxed);
+        if won {
+            // SAFETY: CAS成功，元素归拥有者所有
+            Some(unsafe { elem.assume_init() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for Worker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Stealer<T> {
+    /* 从队首窃取一个元素 */
+    pub fn steal(&self) -> Steal<T> {
+        let top = self.inner.top.0.load(Ordering::Acquire);
+        fence(Ordering::SeqCst);
+        let bottom = self.inner.bottom.0.load(Ordering::Acquire);
+        if top >= bottom {
+            return Steal::Empty;
+        }
+        let buffer = self.inner.buffer.load(Ordering::Acquire);
+        // SAFETY: top < bottom时槽位已初始化；旧数组不会被释放，即使拥有者刚刚扩容也能安全读取
+        let elem = unsafe { (*buffer).read(top) };
+        if self
+            .inner
+            .top
+            .0
+            .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            // 竞争失败，读到的副本不属于自己，直接丢弃而不析构
+            return Steal::Retry;
+        }
+        // SAFETY: CAS成功，元素归窃取者所有
+        Steal::Success(unsafe { elem.assume_init() })
+    }
+
+    // 判断队列是否为空
+    pub fn is_empty(&self) -> bool {
+        let top = self.inner.top.0.load(Ordering::Acquire);
+        let bottom = self.inner.bottom.0.load(Ordering::Acquire);
+        top >= bottom
+    }
+}
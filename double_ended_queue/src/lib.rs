@@ -0,0 +1,105 @@
+/* 双向队列的两种实现：基于环形数组的ArrayDeque和基于双向链表的LinkedListDeque */
+
+use std::collections::VecDeque;
+
+pub mod array_deque;
+pub mod chase_lev;
+pub mod linked_list_deque;
+pub mod thread_pool;
+pub mod window;
+
+/* 双向队列的公共接口，基于数组和基于链表的实现都遵循它 */
+pub trait Deque<T> {
+    /* 从队首到队尾的双向迭代器 */
+    type Iter<'a>: DoubleEndedIterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    // 获取双向队列的长度
+    fn size(&self) -> usize;
+
+    // 判断双向队列是否为空
+    fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    // 队首入队
+    fn push_front(&mut self, elem: T);
+
+    // 队尾入队
+    fn push_back(&mut self, elem: T);
+
+    // 队首出队
+    fn pop_front(&mut self) -> Option<T>;
+
+    // 队尾出队
+    fn pop_back(&mut self) -> Option<T>;
+
+    // 访问队首元素
+    fn front(&self) -> Option<&T>;
+
+    // 访问队尾元素
+    fn back(&self) -> Option<&T>;
+
+    // 访问从队首数起的第index个元素
+    fn get(&self, index: usize) -> Option<&T>;
+
+    // 将队首的n个元素依次移到队尾，要求n <= size
+    fn rotate_left(&mut self, n: usize);
+
+    // 将队尾的n个元素依次移到队首，要求n <= size
+    fn rotate_right(&mut self, n: usize);
+
+    // 从队首到队尾遍历
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+/* 标准库的VecDeque同样是一个双向队列，可以作为其他实现的参照 */
+impl<T> Deque<T> for VecDeque<T> {
+    type Iter<'a> = std::collections::vec_deque::Iter<'a, T> where T: 'a;
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+
+    fn push_front(&mut self, elem: T) {
+        VecDeque::push_front(self, elem)
+    }
+
+    fn push_back(&mut self, elem: T) {
+        VecDeque::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        VecDeque::pop_back(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        VecDeque::front(self)
+    }
+
+    fn back(&self) -> Option<&T> {
+        VecDeque::back(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        VecDeque::get(self, index)
+    }
+
+    fn rotate_left(&mut self, n: usize) {
+        VecDeque::rotate_left(self, n)
+    }
+
+    fn rotate_right(&mut self, n: usize) {
+        VecDeque::rotate_right(self, n)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        VecDeque::iter(self)
+    }
+}
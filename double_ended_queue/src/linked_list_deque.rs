@@ -0,0 +1,405 @@
+/* 基于双向链表实现的双向队列
+    将双向链表的头节点和尾节点视为双向队列的队首和队尾，两端都可以O(1)地添加和删除节点。
+    双向队列独占所有节点，节点由Box::into_raw分配，prev和next都保存为裸指针。 */
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr;
+
+use crate::Deque;
+
+/* 双向链表节点 */
+pub struct ListNode<T> {
+    val: T,
+    next: *mut ListNode<T>, // 后继节点指针
+    prev: *mut ListNode<T>, // 前驱节点指针
+}
+
+impl<T> ListNode<T> {
+    pub fn new(val: T) -> Self {
+        Self {
+            val,
+            next: ptr::null_mut(),
+            prev: ptr::null_mut(),
+        }
+    }
+}
+
+/* 基于双向链表实现的双向队列 */
+pub struct LinkedListDeque<T> {
+    front: *mut ListNode<T>, // 头节点，队列为空时为空指针
+    rear: *mut ListNode<T>,  // 尾节点，队列为空时为空指针
+    que_size: usize,         // 双向队列的长度
+    marker: PhantomData<Box<ListNode<T>>>,
+}
+
+// 双向队列独占所有节点，因此与Box<T>一样可以在线程间转移
+unsafe impl<T: Send> Send for LinkedListDeque<T> {}
+unsafe impl<T: Sync> Sync for LinkedListDeque<T> {}
+
+impl<T> LinkedListDeque<T> {
+    // 构造函数
+    pub fn new() -> Self {
+        Self {
+            front: ptr::null_mut(),
+            rear: ptr::null_mut(),
+            que_size: 0,
+            marker: PhantomData,
+        }
+    }
+
+    // 从离目标较近的一端出发，找到第index个节点
+    fn node_at(&self, index: usize) -> *mut ListNode<T> {
+        if index >= self.que_size {
+            return ptr::null_mut();
+        }
+        // SAFETY: index合法时沿途的节点都非空
+        unsafe {
+            if index < self.que_size / 2 {
+                let mut cur = self.front;
+                for _ in 0..index {
+                    cur = (*cur).next;
+                }
+                cur
+            } else {
+                let mut cur = self.rear;
+                for _ in index + 1..self.que_size {
+                    cur = (*cur).prev;
+                }
+                cur
+            }
+        }
+    }
+
+    /* 把链表首尾相连后，以第index个节点为新的队首重新断开 */
+    fn relink_at(&mut self, index: usize) {
+        if index == 0 || index == self.que_size {
+            return;
+        }
+        let new_front = self.node_at(index);
+        // SAFETY: 0 < index < que_size，首尾节点与新队首及其前驱都非空且互不相同
+        unsafe {
+            let new_rear = (*new_front).prev;
+            (*self.rear).next = self.front;
+            (*self.front).prev = self.rear;
+            (*new_rear).next = ptr::null_mut();
+            (*new_front).prev = ptr::null_mut();
+            self.front = new_front;
+            self.rear = new_rear;
+        }
+    }
+
+    // 访问并修改从队首数起的第index个元素
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // SAFETY: &mut self保证了对节点的独占访问
+        unsafe { self.node_at(index).as_mut().map(|node| &mut node.val) }
+    }
+
+    // 清空双向队列
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    // 从队首到队尾可变遍历
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.front,
+            tail: self.rear,
+            len: self.que_size,
+            marker: PhantomData,
+        }
+    }
+
+    // 返回数组
+    pub fn to_array(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        Deque::iter(self).cloned().collect()
+    }
+}
+
+impl<T> Deque<T> for LinkedListDeque<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+
+    fn size(&self) -> usize {
+        self.que_size
+    }
+
+    fn push_front(&mut self, elem: T) {
+        let node = Box::into_raw(Box::new(ListNode::new(elem)));
+        if self.front.is_null() {
+            // 队列为空，头尾节点都指向新节点
+            self.rear = node;
+        } else {
+            // SAFETY: front非空时指向队列所拥有的头节点
+            unsafe {
+                (*self.front).prev = node;
+                (*node).next = self.front;
+            }
+        }
+        self.front = node;
+        self.que_size += 1;
+    }
+
+    fn push_back(&mut self, elem: T) {
+        let node = Box::into_raw(Box::new(ListNode::new(elem)));
+        if self.rear.is_null() {
+            self.front = node;
+        } else {
+            // SAFETY: rear非空时指向队列所拥有的尾节点
+            unsafe {
+                (*self.rear).next = node;
+                (*node).prev = self.rear;
+            }
+        }
+        self.rear = node;
+        self.que_size += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_null() {
+            return None;
+        }
+        // SAFETY: front非空时指向由Box::into_raw分配、且仅由队列持有的节点
+        let old_front = unsafe { Box::from_raw(self.front) };
+        self.front = old_front.next;
+        if self.front.is_null() {
+            self.rear = ptr::null_mut();
+        } else {
+            // SAFETY: 新的头节点非空
+            unsafe { (*self.front).prev = ptr::null_mut() };
+        }
+        self.que_size -= 1;
+        Some(old_front.val)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        if self.rear.is_null() {
+            return None;
+        }
+        // SAFETY: rear非空时指向由Box::into_raw分配、且仅由队列持有的节点
+        let old_rear = unsafe { Box::from_raw(self.rear) };
+        self.rear = old_rear.prev;
+        if self.rear.is_null() {
+            self.front = ptr::null_mut();
+        } else {
+            // SAFETY: 新的尾节点非空
+            unsafe { (*self.rear).next = ptr::null_mut() };
+        }
+        self.que_size -= 1;
+        Some(old_rear.val)
+    }
+
+    fn front(&self) -> Option<&T> {
+        // SAFETY: 节点的生命周期不短于&self
+        unsafe { self.front.as_ref().map(|node| &node.val) }
+    }
+
+    fn back(&self) -> Option<&T> {
+        // SAFETY: 节点的生命周期不短于&self
+        unsafe { self.rear.as_ref().map(|node| &node.val) }
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        // SAFETY: 节点的生命周期不短于&self
+        unsafe { self.node_at(index).as_ref().map(|node| &node.val) }
+    }
+
+    fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.que_size, "rotate_left: n超过了双向队列长度");
+        // 只需修改指针，无需搬运元素
+        self.relink_at(n);
+    }
+
+    fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.que_size, "rotate_right: n超过了双向队列长度");
+        self.relink_at(self.que_size - n);
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            head: self.front,
+            tail: self.rear,
+            len: self.que_size,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for LinkedListDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedListDeque<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LinkedListDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(Deque::iter(self)).finish()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedListDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for LinkedListDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+/* 双向迭代器，len记录尚未访问的节点数，两端相遇时停止 */
+pub struct Iter<'a, T> {
+    head: *mut ListNode<T>,
+    tail: *mut ListNode<T>,
+    len: usize,
+    marker: PhantomData<&'a ListNode<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: len > 0时head指向一个尚未访问的节点
+        unsafe {
+            let node = &*self.head;
+            self.head = node.next;
+            Some(&node.val)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: len > 0时tail指向一个尚未访问的节点
+        unsafe {
+            let node = &*self.tail;
+            self.tail = node.prev;
+            Some(&node.val)
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/* 可变双向迭代器 */
+pub struct IterMut<'a, T> {
+    head: *mut ListNode<T>,
+    tail: *mut ListNode<T>,
+    len: usize,
+    marker: PhantomData<&'a mut ListNode<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: 每个节点只会被访问一次，因此可变借用互不重叠
+        unsafe {
+            let node = &mut *self.head;
+            self.head = node.next;
+            Some(&mut node.val)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: 每个节点只会被访问一次，因此可变借用互不重叠
+        unsafe {
+            let node = &mut *self.tail;
+            self.tail = node.prev;
+            Some(&mut node.val)
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a LinkedListDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Deque::iter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedListDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/* 按值的双向迭代器 */
+pub struct IntoIter<T>(LinkedListDeque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size(), Some(self.0.size()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedListDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
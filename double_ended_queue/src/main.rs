@@ -1,2 +1,305 @@
 /* 双向队列 */
-/* 在队列中，我们仅能删除头部元素或在尾部删除元素。双向队列提供了更高的灵活性，允许在头部和尾部进行元素的添加和删除的操作。 */
\ No newline at end of file
+/* 在队列中，我们仅能删除头部元素或在尾部删除元素。双向队列提供了更高的灵活性，允许在头部和尾部进行元素的添加和删除的操作。 */
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use double_ended_queue::array_deque::ArrayDeque;
+use double_ended_queue::chase_lev::{Steal, Worker};
+use double_ended_queue::linked_list_deque::LinkedListDeque;
+use double_ended_queue::thread_pool::ThreadPool;
+use double_ended_queue::window::{
+    shortest_subarray, sliding_max, sliding_median, sliding_min, zero_one_bfs, WindowMax,
+};
+use double_ended_queue::Deque;
+
+fn main() {
+    /* 初始化双向队列 */
+    let mut deque: ArrayDeque<i32> = ArrayDeque::new();
+
+    /* 元素入队 */
+    deque.push_back(2);
+    deque.push_back(5);
+    deque.push_back(4);
+    deque.push_front(3);
+    deque.push_front(1);
+    println!("{:?}", deque);
+
+    /* 访问元素 */
+    println!("front: {:?}, back: {:?}, get(2): {:?}", deque.front(), deque.back(), deque.get(2));
+
+    /* 元素出队 */
+    println!("pop_front: {:?}, pop_back: {:?}", deque.pop_front(), deque.pop_back());
+
+    /* 旋转 */
+    let mut linked: LinkedListDeque<i32> = (1..=5).collect();
+    linked.rotate_left(2);
+    println!("{:?}", linked);
+    println!("{:?}", linked.iter().rev().collect::<Vec<_>>());
+
+    conformance::<ArrayDeque<u64>>("ArrayDeque", ArrayDeque::new);
+    conformance::<LinkedListDeque<u64>>("LinkedListDeque", LinkedListDeque::new);
+
+    /* 单调队列与滑动窗口 */
+    let nums = [1, 3, -1, -3, 5, 3, 6, 7];
+    println!("sliding_max: {:?}", sliding_max(&nums, 3));
+    let mut window = WindowMax::new(3);
+    for x in [4, 2, 12, 3] {
+        println!("push {} -> max {:?}", x, window.push(x));
+    }
+    check_window();
+
+    /* 工作窃取 */
+    check_chase_lev();
+    check_thread_pool();
+}
+
+/* 并行归并排序：左右两半通过join并行排序，再合并到临时数组 */
+fn parallel_merge_sort(pool: &ThreadPool, nums: &mut [i32], buf: &mut [i32]) {
+    // 规模较小时直接串行排序，避免任务调度的开销超过收益
+    if nums.len() <= 2048 {
+        nums.sort_unstable();
+        return;
+    }
+    let mid = nums.len() / 2;
+    let (left, right) = nums.split_at_mut(mid);
+    let (left_buf, right_buf) = buf.split_at_mut(mid);
+    pool.join(
+        || parallel_merge_sort(pool, left, left_buf),
+        || parallel_merge_sort(pool, right, right_buf),
+    );
+    // 合并两个有序的半区
+    let (mut i, mut j) = (0, mid);
+    for slot in buf.iter_mut() {
+        if j >= nums.len() || (i < mid && nums[i] <= nums[j]) {
+            *slot = nums[i];
+            i += 1;
+        } else {
+            *slot = nums[j];
+            j += 1;
+        }
+    }
+    nums.copy_from_slice(buf);
+}
+
+/* 拥有者与多个窃取者并发操作同一个Chase–Lev队列，每个元素恰好被取出一次 */
+fn check_chase_lev() {
+    const COUNT: usize = 100_000;
+    const THIEVES: usize = 3;
+    let worker: Worker<usize> = Worker::with_capacity(4);
+    let taken: Arc<Vec<AtomicUsize>> = Arc::new((0..COUNT).map(|_| AtomicUsize::new(0)).collect());
+    let finished = Arc::new(AtomicUsize::new(0));
+    let thieves: Vec<_> = (0..THIEVES)
+        .map(|_| {
+            let stealer = worker.stealer();
+            let taken = Arc::clone(&taken);
+            let finished = Arc::clone(&finished);
+            thread::spawn(move || loop {
+                match stealer.steal() {
+                    Steal::Success(i) => {
+                        taken[i].fetch_add(1, Ordering::Relaxed);
+                    }
+                    Steal::Retry => {}
+                    Steal::Empty if finished.load(Ordering::Acquire) == 1 => break,
+                    Steal::Empty => thread::yield_now(),
+                }
+            })
+        })
+        .collect();
+    // 拥有者交替入队和出队，队列会经历多次扩容
+    for i in 0..COUNT {
+        worker.push(i);
+        if i % 3 == 0 {
+            if let Some(j) = worker.pop() {
+                taken[j].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+    while let Some(j) = worker.pop() {
+        taken[j].fetch_add(1, Ordering::Relaxed);
+    }
+    finished.store(1, Ordering::Release);
+    for thief in thieves {
+        thief.join().unwrap();
+    }
+    assert!(taken.iter().all(|count| count.load(Ordering::Relaxed) == 1));
+    println!("Chase–Lev队列中的每个元素恰好被取出一次");
+}
+
+fn check_thread_pool() {
+    let pool = ThreadPool::new(4);
+
+    // 并行归并排序与标准库排序的结果一致
+    let mut next = lcg(7);
+    let mut nums: Vec<i32> = (0..1_000_000).map(|_| next() as i32).collect();
+    let mut expected = nums.clone();
+    let start = Instant::now();
+    expected.sort_unstable();
+    let serial = start.elapsed();
+    let mut buf = vec![0; nums.len()];
+    let start = Instant::now();
+    parallel_merge_sort(&pool, &mut nums, &mut buf);
+    let parallel = start.elapsed();
+    assert_eq!(nums, expected);
+    println!("串行排序 {:?}，{}线程并行归并排序 {:?}", serial, pool.num_threads(), parallel);
+
+    // 递归的join
+    fn fib(pool: &ThreadPool, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        let (a, b) = pool.join(|| fib(pool, n - 1), || fib(pool, n - 2));
+        a + b
+    }
+    assert_eq!(fib(&pool, 20), 6765);
+
+    // scope中的任务可以借用外部数据，scope返回时全部任务都已完成
+    let chunks: Vec<Vec<u64>> = (0..8).map(|i| (i * 100..(i + 1) * 100).collect()).collect();
+    let total = Mutex::new(0);
+    pool.scope(|s| {
+        for chunk in &chunks {
+            let total = &total;
+            s.spawn(move |_| *total.lock().unwrap() += chunk.iter().sum::<u64>());
+        }
+    });
+    assert_eq!(total.into_inner().unwrap(), (0..800).sum::<u64>());
+
+    // spawn的'static任务
+    let counter = Arc::new(AtomicUsize::new(0));
+    for _ in 0..100 {
+        let counter = Arc::clone(&counter);
+        pool.spawn(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+    drop(pool);
+    assert_eq!(counter.load(Ordering::Relaxed), 100);
+    println!("ThreadPool的spawn、scope和join工作正常");
+}
+
+/* 将滑动窗口工具与暴力解法逐一对比 */
+fn check_window() {
+    let mut next = lcg(42);
+    for _ in 0..200 {
+        let len = (next() % 40) as usize;
+        let nums: Vec<i64> = (0..len).map(|_| (next() % 21) as i64 - 10).collect();
+        for k in 1..=len.max(1) {
+            let windows: Vec<&[i64]> = nums.windows(k).collect();
+            let max: Vec<i64> = windows.iter().map(|w| *w.iter().max().unwrap()).collect();
+            let min: Vec<i64> = windows.iter().map(|w| *w.iter().min().unwrap()).collect();
+            let median: Vec<(i64, i64)> = windows
+                .iter()
+                .map(|w| {
+                    let mut w = w.to_vec();
+                    w.sort();
+                    (w[(k - 1) / 2], w[k / 2])
+                })
+                .collect();
+            assert_eq!(sliding_max(&nums, k), max);
+            assert_eq!(sliding_min(&nums, k), min);
+            assert_eq!(sliding_median(&nums, k), median);
+        }
+        let target = (next() % 30) as i64 - 5;
+        let brute = (1..=len).find(|&k| nums.windows(k).any(|w| w.iter().sum::<i64>() >= target));
+        assert_eq!(shortest_subarray(&nums, target), brute);
+    }
+
+    // 中位数适用于任何全序类型
+    assert_eq!(sliding_median(&["d", "a", "c", "b"], 3), [("c", "c"), ("b", "b")]);
+
+    // 0-1 BFS与Bellman-Ford式松弛的结果比较
+    let grid: Vec<Vec<u8>> = (0..12).map(|_| (0..15).map(|_| next().is_multiple_of(3) as u8).collect()).collect();
+    let dist = zero_one_bfs(&grid, (0, 0));
+    let mut expected = vec![vec![usize::MAX; 15]; 12];
+    expected[0][0] = 0;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for r in 0..12usize {
+            for c in 0..15usize {
+                let neighbors = [(r.wrapping_sub(1), c), (r + 1, c), (r, c.wrapping_sub(1)), (r, c + 1)];
+                for (nr, nc) in neighbors {
+                    if nr >= 12 || nc >= 15 || expected[nr][nc] == usize::MAX {
+                        continue;
+                    }
+                    let d = expected[nr][nc] + grid[r][c] as usize;
+                    if d < expected[r][c] {
+                        expected[r][c] = d;
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+    assert_eq!(dist, expected);
+    println!("滑动窗口工具与暴力解法的结果一致");
+}
+
+/* 线性同余生成器(LCG)：state = state * a + c (mod 2^64)，a与c取自Knuth的MMIX。
+    低位的周期很短，因此只返回高31位。检查只需要可以重现的输入，不需要统计意义上的随机性。 */
+fn lcg(mut state: u64) -> impl FnMut() -> u64 {
+    move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        state >> 33
+    }
+}
+
+/* 一致性测试：用同一串伪随机操作同时驱动被测双向队列和VecDeque，每一步都比较两者的状态 */
+fn conformance<D: Deque<u64>>(name: &str, new: fn() -> D) {
+    let mut deque = new();
+    let mut model: VecDeque<u64> = VecDeque::new();
+    let mut next = lcg(0x9e37_79b9_7f4a_7c15);
+    for step in 0..20_000u64 {
+        let r = next();
+        match r % 9 {
+            0 | 1 => {
+                deque.push_front(step);
+                model.push_front(step);
+            }
+            2 | 3 => {
+                deque.push_back(step);
+                model.push_back(step);
+            }
+            4 => assert_eq!(deque.pop_front(), model.pop_front()),
+            5 => assert_eq!(deque.pop_back(), model.pop_back()),
+            6 => {
+                let n = (r >> 8) as usize % (model.len() + 1);
+                deque.rotate_left(n);
+                model.rotate_left(n);
+            }
+            7 => {
+                let n = (r >> 8) as usize % (model.len() + 1);
+                deque.rotate_right(n);
+                model.rotate_right(n);
+            }
+            _ => {
+                let i = (r >> 8) as usize % (model.len() + 2);
+                assert_eq!(deque.get(i), model.get(i));
+            }
+        }
+        assert_eq!(deque.size(), model.len());
+        assert_eq!(deque.is_empty(), model.is_empty());
+        assert_eq!(deque.front(), model.front());
+        assert_eq!(deque.back(), model.back());
+    }
+    assert!(deque.iter().eq(model.iter()));
+    assert!(deque.iter().rev().eq(model.iter().rev()));
+    // 从两端交替消费双向迭代器
+    let mut it = deque.iter();
+    let mut expected = model.iter();
+    loop {
+        let (a, b) = (it.next(), expected.next());
+        assert_eq!(a, b);
+        let (c, d) = (it.next_back(), expected.next_back());
+        assert_eq!(c, d);
+        if b.is_none() || d.is_none() {
+            break;
+        }
+    }
+    println!("{}与VecDeque的行为一致", name);
+}
@@ -0,0 +1,409 @@
+/* 基于工作窃取的线程池
+    每个工作线程拥有一个Chase–Lev双向队列，自己产生的任务放在队尾并从队尾取出；自己的队列空了就去其他线程的队首窃取。
+    从线程池外部提交的任务先放入一个公共的注入队列，由空闲的工作线程领取。
+        1.spawn：提交一个独立的'static任务。
+        2.join：并行执行两个闭包并等待二者完成，闭包可以借用调用方栈上的数据，适合递归的分治任务。
+        3.scope：在作用域内提交任意多个可以借用外部数据的任务，作用域结束前等待它们全部完成。
+    等待其他任务完成的线程不会空等，而是继续执行队列中的任务，因此递归的join不会因为线程数有限而死锁。 */
+
+use std::any::Any;
+use std::cell::{RefCell, UnsafeCell};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle, Thread};
+use std::time::Duration;
+
+use crate::chase_lev::{Steal, Stealer, Worker};
+
+/* 类型擦除后的任务：一个数据指针加上执行它的函数 */
+struct JobRef {
+    pointer: *const (),
+    execute: unsafe fn(*const ()),
+}
+
+// 任务的所有者保证任务在被执行前一直有效，且只会被执行一次
+unsafe impl Send for JobRef {}
+
+impl JobRef {
+    unsafe fn execute(self) {
+        (self.execute)(self.pointer)
+    }
+}
+
+/* 堆上的任务，执行后释放 */
+struct HeapJob<F> {
+    func: F,
+}
+
+impl<F: FnOnce() + Send> HeapJob<F> {
+    fn into_job_ref(self: Box<Self>) -> JobRef {
+        unsafe fn execute<F: FnOnce()>(pointer: *const ()) {
+            let job = Box::from_raw(pointer as *mut HeapJob<F>);
+            (job.func)();
+        }
+        JobRef {
+            pointer: Box::into_raw(self) as *const (),
+            execute: execute::<F>,
+        }
+    }
+}
+
+/* 栈上的任务，由join使用：调用方在任务完成前不会返回，因此任务可以借用调用方的数据 */
+struct StackJob<F, R> {
+    func: UnsafeCell<Option<F>>,
+    result: UnsafeCell<Option<thread::Result<R>>>,
+    done: AtomicBool,
+    waiter: Option<Thread>, // 阻塞等待该任务的外部线程，任务完成时唤醒它
+}
+
+impl<F: FnOnce() -> R + Send, R: Send> StackJob<F, R> {
+    fn new(func: F) -> Self {
+        Self {
+            func: UnsafeCell::new(Some(func)),
+            result: UnsafeCell::new(None),
+            done: AtomicBool::new(false),
+            waiter: None,
+        }
+    }
+
+    fn as_job_ref(&self) -> JobRef {
+        unsafe fn execute<F: FnOnce() -> R, R>(pointer: *const ()) {
+            let job = &*(pointer as *const StackJob<F, R>);
+            let func = (*job.func.get()).take().expect("任务只会被执行一次");
+            *job.result.get() = Some(panic::catch_unwind(AssertUnwindSafe(func)));
+            // done变为true之后调用方随时可能返回并释放任务，因此先取出要唤醒的线程
+            let waiter = job.waiter.clone();
+            job.done.store(true, Ordering::Release);
+            if let Some(waiter) = waiter {
+                waiter.unpark();
+            }
+        }
+        JobRef {
+            pointer: self as *const Self as *const (),
+            execute: execute::<F, R>,
+        }
+    }
+
+    // 在当前线程直接执行(任务尚未被窃取时)
+    fn run_inline(self) -> R {
+        let func = self.func.into_inner().expect("任务只会被执行一次");
+        func()
+    }
+
+    fn into_result(self) -> R {
+        match self.result.into_inner().expect("任务已经完成") {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+/* 所有线程共享的状态 */
+struct Shared {
+    injector: Mutex<VecDeque<JobRef>>, // 来自线程池外部的任务
+    stealers: Vec<Stealer<JobRef>>,
+    sleep_lock: Mutex<()>,
+    sleep_cond: Condvar,
+    terminate: AtomicBool,
+}
+
+impl Shared {
+    fn inject(&self, job: JobRef) {
+        self.injector
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push_back(job);
+        self.sleep_cond.notify_one();
+    }
+
+    // 从注入队列或其他线程的队列中找一个任务，skip为当前线程的编号
+    fn find_work(&self, skip: Option<usize>) -> Option<JobRef> {
+        if let Some(job) = self
+            .injector
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+        {
+            return Some(job);
+        }
+        let n = self.stealers.len();
+        let start = skip.map_or(0, |i| i + 1);
+        for k in 0..n {
+            let i = (start + k) % n;
+            if Some(i) == skip {
+                continue;
+            }
+            loop {
+                match self.stealers[i].steal() {
+                    Steal::Success(job) => return Some(job),
+                    Steal::Empty => break,
+                    Steal::Retry => continue,
+                }
+            }
+        }
+        None
+    }
+}
+
+/* 工作线程的本地状态，保存在线程局部变量中 */
+struct WorkerThread {
+    shared: Arc<Shared>,
+    index: usize,
+    deque: Worker<JobRef>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Rc<WorkerThread>>> = const { RefCell::new(None) };
+}
+
+impl WorkerThread {
+    // 当前线程若是shared所属线程池的工作线程，则返回其本地状态
+    fn current(shared: &Arc<Shared>) -> Option<Rc<WorkerThread>> {
+        CURRENT.with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .filter(|worker| Arc::ptr_eq(&worker.shared, shared))
+                .cloned()
+        })
+    }
+
+    fn find_work(&self) -> Option<JobRef> {
+        self.deque
+            .pop()
+            .or_else(|| self.shared.find_work(Some(self.index)))
+    }
+
+    // 在done变为true之前，不断执行其他任务
+    fn wait_until(&self, done: &dyn Fn() -> bool) {
+        while !done() {
+            match self.find_work() {
+                // SAFETY: 任务来自队列，只会被执行一次
+                Some(job) => unsafe { job.execute() },
+                None => thread::yield_now(),
+            }
+        }
+    }
+
+    fn main_loop(&self) {
+        loop {
+            if let Some(job) = self.find_work() {
+                // SAFETY: 同上
+                unsafe { job.execute() };
+                continue;
+            }
+            if self.shared.terminate.load(Ordering::Acquire) {
+                return;
+            }
+            // 没有任务时短暂睡眠，有新任务注入时会被唤醒
+            let guard = self
+                .shared
+                .sleep_lock
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let _ = self
+                .shared
+                .sleep_cond
+                .wait_timeout(guard, Duration::from_millis(1));
+        }
+    }
+}
+
+/* 固定线程数的工作窃取线程池 */
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    // 创建包含num_threads个工作线程的线程池
+    pub fn new(num_threads: usize) -> Self {
+        assert!(num_threads > 0, "线程池至少需要一个线程");
+        let workers: Vec<Worker<JobRef>> = (0..num_threads).map(|_| Worker::new()).collect();
+        let shared = Arc::new(Shared {
+            injector: Mutex::new(VecDeque::new()),
+            stealers: workers.iter().map(|w| w.stealer()).collect(),
+            sleep_lock: Mutex::new(()),
+            sleep_cond: Condvar::new(),
+            terminate: AtomicBool::new(false),
+        });
+        let handles = workers
+            .into_iter()
+            .enumerate()
+            .map(|(index, deque)| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let worker = Rc::new(WorkerThread {
+                        shared,
+                        index,
+                        deque,
+                    });
+                    CURRENT.with(|current| *current.borrow_mut() = Some(Rc::clone(&worker)));
+                    worker.main_loop();
+                    CURRENT.with(|current| current.borrow_mut().take());
+                })
+            })
+            .collect();
+        Self { shared, handles }
+    }
+
+    // 工作线程数量
+    pub fn num_threads(&self) -> usize {
+        self.handles.len()
+    }
+
+    // 提交一个任务：在工作线程中调用时放入本地队列，否则放入注入队列
+    fn push_job(&self, job: JobRef) {
+        match WorkerThread::current(&self.shared) {
+            Some(worker) => {
+                worker.deque.push(job);
+                self.shared.sleep_cond.notify_one();
+            }
+            None => self.shared.inject(job),
+        }
+    }
+
+    /* 提交一个独立任务，不等待它完成；任务中的panic会被捕获并丢弃 */
+    pub fn spawn<F: FnOnce() + Send + 'static>(&self, func: F) {
+        let job = Box::new(HeapJob {
+            func: move || {
+                let _ = panic::catch_unwind(AssertUnwindSafe(func));
+            },
+        });
+        self.push_job(job.into_job_ref());
+    }
+
+    /* 在线程池中执行func并阻塞等待结果 */
+    pub fn install<F: FnOnce() -> R + Send, R: Send>(&self, func: F) -> R {
+        if WorkerThread::current(&self.shared).is_some() {
+            return func();
+        }
+        let mut job = StackJob::new(func);
+        job.waiter = Some(thread::current());
+        self.shared.inject(job.as_job_ref());
+        // 外部线程不参与执行，挂起等待，由执行任务的线程唤醒；park可能无故返回，因此循环检查
+        while !job.done.load(Ordering::Acquire) {
+            thread::park();
+        }
+        job.into_result()
+    }
+
+    /* 并行执行a和b，返回二者的结果 */
+    pub fn join<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        let worker = match WorkerThread::current(&self.shared) {
+            Some(worker) => worker,
+            None => return self.install(|| self.join(a, b)),
+        };
+        // 把b放到本地队列，让空闲线程有机会窃取它，当前线程先执行a
+        let job_b = StackJob::new(b);
+        let job_b_ref = job_b.as_job_ref();
+        let pointer = job_b_ref.pointer;
+        worker.deque.push(job_b_ref);
+        self.shared.sleep_cond.notify_one();
+        let result_a = panic::catch_unwind(AssertUnwindSafe(a));
+
+        // 若b还没有被窃取，它一定仍在本地队列中，弹出后直接执行
+        while !job_b.done.load(Ordering::Acquire) {
+            match worker.deque.pop() {
+                Some(job) if job.pointer == pointer => {
+                    let result_b = job_b.run_inline();
+                    return (Self::unwrap(result_a), result_b);
+                }
+                // SAFETY: 其他任务来自本地队列，只会被执行一次
+                Some(job) => unsafe { job.execute() },
+                None => {
+                    // b已被窃取，执行其他任务直到它完成
+                    worker.wait_until(&|| job_b.done.load(Ordering::Acquire));
+                }
+            }
+        }
+        (Self::unwrap(result_a), job_b.into_result())
+    }
+
+    fn unwrap<R>(result: thread::Result<R>) -> R {
+        result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+
+    /* 创建一个作用域，作用域内提交的任务可以借用外部数据，scope返回前等待所有任务完成 */
+    pub fn scope<'scope, F, R>(&'scope self, func: F) -> R
+    where
+        F: FnOnce(&Scope<'scope>) -> R + Send,
+        R: Send,
+    {
+        self.install(|| {
+            let scope = Scope {
+                pool: self,
+                pending: Arc::new(AtomicUsize::new(0)),
+                panic: Arc::new(Mutex::new(None)),
+                marker: PhantomData,
+            };
+            let result = panic::catch_unwind(AssertUnwindSafe(|| func(&scope)));
+            // 等待作用域内的全部任务完成
+            let worker = WorkerThread::current(&self.shared).expect("install保证在工作线程中执行");
+            worker.wait_until(&|| scope.pending.load(Ordering::Acquire) == 0);
+            if let Some(payload) = scope.panic.lock().unwrap_or_else(|e| e.into_inner()).take() {
+                panic::resume_unwind(payload);
+            }
+            Self::unwrap(result)
+        })
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shared.terminate.store(true, Ordering::Release);
+        self.shared.sleep_cond.notify_all();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/* 作用域，'scope为任务可以借用的数据的生命周期 */
+pub struct Scope<'scope> {
+    pool: &'scope ThreadPool,
+    pending: Arc<AtomicUsize>,
+    #[allow(clippy::type_complexity)]
+    panic: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+    marker: PhantomData<&'scope mut &'scope ()>,
+}
+
+impl<'scope> Scope<'scope> {
+    /* 在作用域内提交一个任务 */
+    pub fn spawn<F>(&self, func: F)
+    where
+        F: FnOnce(&Scope<'scope>) + Send + 'scope,
+    {
+        self.pending.fetch_add(1, Ordering::AcqRel);
+        let scope = Scope {
+            pool: self.pool,
+            pending: Arc::clone(&self.pending),
+            panic: Arc::clone(&self.panic),
+            marker: PhantomData,
+        };
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| func(&scope))) {
+                scope
+                    .panic
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get_or_insert(payload);
+            }
+            scope.pending.fetch_sub(1, Ordering::AcqRel);
+        });
+        // SAFETY: scope()在pending归零之前不会返回，因此任务借用的数据在任务执行期间一直有效
+        let job: Box<dyn FnOnce() + Send + 'static> = unsafe { mem::transmute(job) };
+        self.pool.push_job(Box::new(HeapJob { func: job }).into_job_ref());
+    }
+}
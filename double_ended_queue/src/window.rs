@@ -0,0 +1,242 @@
+/* 滑动窗口与单调双向队列
+    单调队列在双向队列中维护一个单调的元素序列：新元素从队尾入队前，先把队尾所有“不可能再成为答案”的元素弹出；
+    队首元素一旦滑出窗口，就从队首弹出。每个元素最多入队、出队各一次，因此处理n个元素的总时间为O(n)。 */
+
+use std::collections::BTreeSet;
+
+use crate::array_deque::ArrayDeque;
+use crate::Deque;
+
+/* 单调窗口：队列中保存(下标, 值)，从队首到队尾满足 keep(前一个, 后一个) */
+struct MonotonicWindow<T> {
+    deque: ArrayDeque<(usize, T)>,
+    size: usize,                 // 窗口大小
+    count: usize,                // 已经输入的元素个数
+    keep: fn(&T, &T) -> bool,    // 队尾元素应当保留时返回true
+}
+
+impl<T> MonotonicWindow<T> {
+    fn new(size: usize, keep: fn(&T, &T) -> bool) -> Self {
+        assert!(size > 0, "窗口大小必须大于0");
+        Self {
+            deque: ArrayDeque::new(),
+            size,
+            count: 0,
+            keep,
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        // 弹出队尾所有不可能再成为答案的元素
+        while let Some((_, last)) = self.deque.back() {
+            if (self.keep)(last, &value) {
+                break;
+            }
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.count, value));
+        self.count += 1;
+        // 弹出已经滑出窗口的队首元素
+        while let Some(&(index, _)) = self.deque.front() {
+            if index + self.size >= self.count {
+                break;
+            }
+            self.deque.pop_front();
+        }
+    }
+
+    fn get(&self) -> Option<&T> {
+        self.deque.front().map(|(_, value)| value)
+    }
+
+    fn is_full(&self) -> bool {
+        self.count >= self.size
+    }
+}
+
+/* 流式滑动窗口最大值：每次输入一个值，随时查询最近size个值中的最大值 */
+pub struct WindowMax<T> {
+    window: MonotonicWindow<T>,
+}
+
+impl<T: PartialOrd> WindowMax<T> {
+    pub fn new(size: usize) -> Self {
+        // 队列单调递减，队首即为最大值；相等的旧元素会比新元素先滑出窗口，因此可以丢弃
+        Self {
+            window: MonotonicWindow::new(size, |last, value| last > value),
+        }
+    }
+
+    // 输入一个值，窗口已满时返回当前窗口的最大值
+    pub fn push(&mut self, value: T) -> Option<&T> {
+        self.window.push(value);
+        if self.window.is_full() {
+            self.window.get()
+        } else {
+            None
+        }
+    }
+
+    // 最近size个值(不足size个时为全部值)中的最大值
+    pub fn max(&self) -> Option<&T> {
+        self.window.get()
+    }
+}
+
+/* 流式滑动窗口最小值 */
+pub struct WindowMin<T> {
+    window: MonotonicWindow<T>,
+}
+
+impl<T: PartialOrd> WindowMin<T> {
+    pub fn new(size: usize) -> Self {
+        // 队列单调递增，队首即为最小值
+        Self {
+            window: MonotonicWindow::new(size, |last, value| last < value),
+        }
+    }
+
+    // 输入一个值，窗口已满时返回当前窗口的最小值
+    pub fn push(&mut self, value: T) -> Option<&T> {
+        self.window.push(value);
+        if self.window.is_full() {
+            self.window.get()
+        } else {
+            None
+        }
+    }
+
+    // 最近size个值(不足size个时为全部值)中的最小值
+    pub fn min(&self) -> Option<&T> {
+        self.window.get()
+    }
+}
+
+/* 滑动窗口最大值：返回每个长度为k的窗口的最大值，共nums.len() - k + 1个 */
+pub fn sliding_max<T: PartialOrd + Clone>(nums: &[T], k: usize) -> Vec<T> {
+    let mut window = WindowMax::new(k);
+    nums.iter()
+        .filter_map(|x| window.push(x.clone()).cloned())
+        .collect()
+}
+
+/* 滑动窗口最小值 */
+pub fn sliding_min<T: PartialOrd + Clone>(nums: &[T], k: usize) -> Vec<T> {
+    let mut window = WindowMin::new(k);
+    nums.iter()
+        .filter_map(|x| window.push(x.clone()).cloned())
+        .collect()
+}
+
+/* 滑动窗口中位数：中位数不具有单调性，单调队列帮不上忙，这里改用两个有序集合把窗口分成较小的一半low与较大的一半high，
+    low的元素个数为窗口大小的一半(向上取整)，low的最大值与high的最小值就是中间的两个数。
+    集合中存放(值, 下标)，下标用来区分相等的值，使滑出窗口的元素可以按(值, 下标)精确删除。每次滑动的时间复杂度为O(log k)。
+    返回每个窗口中间的两个数(下中位数, 上中位数)，k为奇数时两者相同；需要平均值时由调用者自行计算。 */
+pub fn sliding_median<T: Ord + Copy>(nums: &[T], k: usize) -> Vec<(T, T)> {
+    assert!(k > 0, "窗口大小必须大于0");
+    let mut low: BTreeSet<(T, usize)> = BTreeSet::new();
+    let mut high: BTreeSet<(T, usize)> = BTreeSet::new();
+    let mut res = Vec::new();
+    for (i, &x) in nums.iter().enumerate() {
+        // 新元素先放入low，再把low中最大的元素移入high，保证low中的元素都不大于high中的元素
+        low.insert((x, i));
+        let top = low.pop_last().expect("刚插入过元素");
+        high.insert(top);
+        // 删除滑出窗口的元素
+        if i >= k {
+            let old = (nums[i - k], i - k);
+            if !low.remove(&old) {
+                high.remove(&old);
+            }
+        }
+        // 重新平衡两个集合的大小
+        let half = (i + 1).min(k).div_ceil(2);
+        while low.len() < half {
+            let first = high.pop_first().expect("窗口中的元素足够多");
+            low.insert(first);
+        }
+        while low.len() > half {
+            let last = low.pop_last().expect("low不为空");
+            high.insert(last);
+        }
+        if i + 1 >= k {
+            let lower = low.last().expect("窗口不为空").0;
+            let upper = if k % 2 == 1 {
+                lower
+            } else {
+                high.first().expect("k为偶数时high不为空").0
+            };
+            res.push((lower, upper));
+        }
+    }
+    res
+}
+
+/* 和至少为target的最短子数组(元素可以为负数)
+    对前缀和维护一个单调递增的队列：若prefix[j] - prefix[队首] >= target，则队首对应的最短子数组已经找到，可以弹出；
+    若prefix[j] <= prefix[队尾]，则队尾永远不会比j更优，也可以弹出。 */
+pub fn shortest_subarray(nums: &[i64], target: i64) -> Option<usize> {
+    let mut prefix = Vec::with_capacity(nums.len() + 1);
+    prefix.push(0i64);
+    for &x in nums {
+        prefix.push(prefix[prefix.len() - 1] + x);
+    }
+    let mut best: Option<usize> = None;
+    let mut deque: ArrayDeque<usize> = ArrayDeque::new();
+    for (j, &sum) in prefix.iter().enumerate() {
+        while let Some(&i) = deque.front() {
+            if sum - prefix[i] < target {
+                break;
+            }
+            best = Some(best.map_or(j - i, |b| b.min(j - i)));
+            deque.pop_front();
+        }
+        while let Some(&i) = deque.back() {
+            if prefix[i] < sum {
+                break;
+            }
+            deque.pop_back();
+        }
+        deque.push_back(j);
+    }
+    best
+}
+
+/* 0-1 BFS：网格中进入一个格子的代价为该格子的值(0或1)，求从start出发到每个格子的最小代价。
+    代价为0的边把邻居放到队首，代价为1的边放到队尾，这样双向队列始终按距离非递减排列，效果等同于Dijkstra但只需O(V+E)。 */
+pub fn zero_one_bfs(grid: &[Vec<u8>], start: (usize, usize)) -> Vec<Vec<usize>> {
+    let rows = grid.len();
+    let cols = grid.first().map_or(0, |row| row.len());
+    let mut dist = vec![vec![usize::MAX; cols]; rows];
+    if start.0 >= rows || start.1 >= cols {
+        return dist;
+    }
+    let mut deque: ArrayDeque<(usize, usize)> = ArrayDeque::new();
+    dist[start.0][start.1] = 0;
+    deque.push_back(start);
+    while let Some((r, c)) = deque.pop_front() {
+        let d = dist[r][c];
+        let neighbors = [
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ];
+        for (nr, nc) in neighbors {
+            if nr >= rows || nc >= cols {
+                continue;
+            }
+            let w = grid[nr][nc] as usize;
+            assert!(w <= 1, "0-1 BFS的格子代价只能是0或1");
+            if d + w < dist[nr][nc] {
+                dist[nr][nc] = d + w;
+                if w == 0 {
+                    deque.push_front((nr, nc));
+                } else {
+                    deque.push_back((nr, nc));
+                }
+            }
+        }
+    }
+    dist
+}
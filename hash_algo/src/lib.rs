@@ -0,0 +1,2 @@
+/* 哈希算法的实现 */
+pub mod seeded;
@@ -26,7 +26,58 @@
     然而，该哈希算法过于简单，所有后两位相等的key的输出都相同，因此我们可以很容易地从哈希值反推出可用的key，从而破解密码。
 */
 
+use std::hash::BuildHasher;
+
+use hash_algo::seeded::SeededState;
 
 fn main() {
     println!("哈希算法的学习");
+
+    /* 同一个key在不同种子下的哈希值 */
+    for state in SeededState::family(2024, 3) {
+        println!(
+            "seed {:#018x}: hash(\"hello\") = {:#018x}",
+            state.seed(),
+            state.hash_one("hello")
+        );
+    }
+
+    check_seeded_hash();
+}
+
+/* 带种子哈希的基本性质：确定性、均匀分布、不同种子之间相互独立、雪崩效应 */
+fn check_seeded_hash() {
+    let [a, b]: [SeededState; 2] = SeededState::family(7, 2).try_into().unwrap();
+    assert_eq!(a.hash_one(12345u64), a.hash_one(12345u64));
+    assert_ne!(a.hash_one(12345u64), b.hash_one(12345u64));
+    assert_ne!(
+        SeededState::new(0).hash_one(0u64),
+        SeededState::new(1).hash_one(0u64)
+    );
+
+    // 连续的key也应当均匀地落入64个桶，每个桶的数量与平均值相差不超过10%
+    let n = 64 * 2000;
+    let mut buckets = [0usize; 64];
+    for key in 0..n as u64 {
+        buckets[(a.hash_one(key) % 64) as usize] += 1;
+    }
+    assert!(buckets.iter().all(|&count| count.abs_diff(2000) < 200));
+
+    // 两个种子给出的桶号组合也应当均匀，否则布谷鸟哈希的两个候选桶会高度相关
+    let mut pairs = [0usize; 256];
+    for key in 0..n as u64 {
+        pairs[(a.hash_one(key) % 16 * 16 + b.hash_one(key) % 16) as usize] += 1;
+    }
+    assert!(pairs.iter().all(|&count| count.abs_diff(500) < 125));
+
+    // 输入翻转一位，输出平均翻转约一半(32位)
+    let mut flipped = 0;
+    for key in 0..1000u64 {
+        for bit in 0..64 {
+            flipped += (a.hash_one(key) ^ a.hash_one(key ^ (1 << bit))).count_ones();
+        }
+    }
+    let avg = flipped as f64 / (1000 * 64) as f64;
+    assert!((avg - 32.0).abs() < 1.0);
+    println!("带种子的哈希函数分布均匀，平均翻转 {:.2} 位", avg);
 }
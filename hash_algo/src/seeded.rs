@@ -0,0 +1,83 @@
+/* 带种子的哈希函数
+    布谷鸟哈希等结构需要多个“互相独立”的哈希函数，并且在插入失败时能够换一组新的哈希函数重新开始。
+    最简单的办法是让同一个哈希算法接受一个种子：种子不同，输出就像来自不同的哈希函数。
+    这里的实现分两步：
+        1.FNV-1a：从“初始值 ^ 种子”出发，逐个字节做 异或 + 乘以质数，速度快、实现简单，但低位的随机性较差。
+        2.fmix64：MurmurHash3的最终混合函数，经过几轮异或移位和乘法，使输入的每一位都影响输出的每一位(雪崩效应)。
+    它不具备密码学意义上的安全性，只用于哈希表。 */
+
+use std::hash::{BuildHasher, Hasher};
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/* MurmurHash3的64位最终混合函数 */
+pub fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^ (k >> 33)
+}
+
+/* SplitMix64：由一个状态生成一串互不相关的种子 */
+pub fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/* 带种子的哈希器 */
+#[derive(Debug, Clone)]
+pub struct SeededHasher {
+    state: u64,
+}
+
+impl Hasher for SeededHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        fmix64(self.state)
+    }
+}
+
+/* 带种子的哈希算法，实现了BuildHasher，可以直接交给各个哈希表使用 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SeededState {
+    seed: u64,
+}
+
+impl SeededState {
+    // 构造函数
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    // 获取种子
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /* 由一个种子派生出d个哈希函数 */
+    pub fn family(mut seed: u64, d: usize) -> Vec<SeededState> {
+        (0..d).map(|_| Self::new(splitmix64(&mut seed))).collect()
+    }
+}
+
+impl BuildHasher for SeededState {
+    type Hasher = SeededHasher;
+
+    fn build_hasher(&self) -> SeededHasher {
+        // 种子先经过混合再与初始值异或，相邻的种子也会得到差别很大的初始状态
+        SeededHasher {
+            state: FNV_OFFSET ^ fmix64(self.seed),
+        }
+    }
+}
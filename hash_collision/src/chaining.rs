@@ -0,0 +1,589 @@
+/* 1.链式地址
+        在原始哈希表中，每个桶仅能存储一个键值对。链式地址(seperate chaining)将单个元素转换为链表，将键值对作为链表节点，将所有发生冲突的键值对都存储在一个链表中。
+        
+        基于链式地址实现的哈希表的操作方法发生了如下变化：
+            1.查询元素：输入key，经过哈希函数得到桶索引，即可访问链表头节点，然后遍历链表并对比key以查找目标键值对。
+            2.添加元素：首先通过哈希函数访问链表头节点，然后将节点(键值对)添加到链表中。
+            3.根据哈希函数的结果访问链表头部，接着遍历链表以查找目标节点并将其删除。
+            
+        链式地址存在以下局限性：
+            1.占用空间增大：链表需要包含节点指针，它相比数组更加耗费内存空间。
+            2.查询效率降低：因为需要线性遍历链表来查找对应元素。
+            
+        以下给出了链式地址哈希表的简单实现，需要注意两点
+            1.使用列表(动态数组)代替链表，从而简化代码。在这种设定下，哈希表(数组)包含多个bucket，每个bucket都是一个列表。
+            2.以下实现包含哈希表扩容与缩容方法，由ResizePolicy决定(见resize.rs)。默认当负载因子超过2/3时，我们将哈希表扩容至原先的2倍；
+            删除后负载因子低于1/6时，缩容至负载因子回到1/3左右。
+
+        扩容时一次性搬运全部键值对，哈希表很大时这一次插入会卡顿很久。渐进式扩容(与Redis的rehash相同)把搬运分摊到之后的每次操作中：
+            1.扩容时只分配新的桶数组，旧的桶数组保留下来，并记录下一个待搬运的旧桶rehash_idx。
+            2.此后每次插入、删除或get_mut都顺便搬运若干个旧桶，直到旧桶全部搬完，再释放旧的桶数组。
+            3.搬运期间键值对可能在任意一个桶数组中，查询需要同时查看两个桶数组；新的键值对只写入新的桶数组。
+        每次操作搬运的桶数有上界，代价是搬运期间同时占用两个桶数组的内存。搬运必须在下一次扩容或缩容之前完成，否则下一次扩容只能一口气搬完剩下的旧桶。
+        因此每次搬运的桶数取 step 与 旧桶数 / 距离下一次扩容或缩容的操作数 中的较大者。默认策略下，扩容后容量为c时，c/2个旧桶要在c/6次删除
+        (负载因子从1/3降到1/6)之内搬完，即每次3个桶；缩容后旧桶多、余量小，约为13个桶。
+        分配新的桶数组仍然与容量成正比，但只需写入空桶，比逐个重新计算哈希并搬运键值对快得多。
+
+        树化(与Java 8的HashMap相同)：若大量key落入同一个桶(例如被恶意构造的key)，查询会退化为O(n)的遍历。
+        当一个桶中的键值对超过TREEIFY_THRESHOLD个时，把列表转换为按完整哈希值排序的平衡树(标准库的BTreeMap)，查询变为O(log n)；
+        删除后少于UNTREEIFY_THRESHOLD个时再转换回列表。两个阈值之间留有间隔，避免在阈值附近反复转换。
+        桶索引只取哈希值的低位，构造“桶索引相同”的key很容易；而完整的64位哈希值相同的key极少，它们在树中共用一个列表。 */
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::resize::ResizePolicy;
+use crate::stats::MapStats;
+use crate::Pair;
+
+const TREEIFY_THRESHOLD: usize = 8; // 列表长度超过该值时转换为树
+const UNTREEIFY_THRESHOLD: usize = 6; // 树的大小不超过该值时转换回列表
+
+/* 桶：较短时是列表，过长时是按哈希值排序的平衡树。树很少出现，放在堆上，使空桶与列表一样小 */
+enum Bucket<K, V> {
+    List(Vec<Pair<K, V>>),
+    Tree(Box<Tree<K, V>>),
+}
+
+/* 树化的桶 */
+struct Tree<K, V> {
+    map: BTreeMap<u64, Vec<Pair<K, V>>>, // 哈希值 -> 哈希值相同的键值对
+    len: usize,                          // 键值对总数
+}
+
+impl<K: Eq, V> Bucket<K, V> {
+    fn new() -> Self {
+        Bucket::List(Vec::new())
+    }
+
+    // 哈希值为hash的键值对所在的列表
+    fn chain(&self, hash: u64) -> &[Pair<K, V>] {
+        match self {
+            Bucket::List(list) => list,
+            Bucket::Tree(tree) => tree.map.get(&hash).map_or(&[], Vec::as_slice),
+        }
+    }
+
+    fn chain_mut(&mut self, hash: u64) -> Option<&mut Vec<Pair<K, V>>> {
+        match self {
+            Bucket::List(list) => Some(list),
+            Bucket::Tree(tree) => tree.map.get_mut(&hash),
+        }
+    }
+
+    /* 查找键值对 */
+    fn find<Q>(&self, hash: u64, key: &Q) -> Option<&Pair<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.chain(hash)
+            .iter()
+            .find(|pair| pair.key.borrow() == key)
+    }
+
+    fn find_mut<Q>(&mut self, hash: u64, key: &Q) -> Option<&mut Pair<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.chain_mut(hash)?
+            .iter_mut()
+            .find(|pair| pair.key.borrow() == key)
+    }
+
+    /* 放入一个确定不存在的键值对，列表过长时转换为树；hash_of用于计算列表中已有键值对的哈希值 */
+    fn push(&mut self, hash: u64, pair: Pair<K, V>, hash_of: impl Fn(&K) -> u64) {
+        match self {
+            Bucket::List(list) => {
+                list.push(pair);
+                if list.len() > TREEIFY_THRESHOLD {
+                    let mut tree = Tree {
+                        map: BTreeMap::new(),
+                        len: list.len(),
+                    };
+                    for pair in list.drain(..) {
+                        tree.map.entry(hash_of(&pair.key)).or_default().push(pair);
+                    }
+                    *self = Bucket::Tree(Box::new(tree));
+                }
+            }
+            Bucket::Tree(tree) => {
+                tree.map.entry(hash).or_default().push(pair);
+                tree.len += 1;
+            }
+        }
+    }
+
+    /* 删除键值对，树变小时转换回列表 */
+    fn remove<Q>(&mut self, hash: u64, key: &Q) -> Option<Pair<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let chain = self.chain_mut(hash)?;
+        let i = chain.iter().position(|pair| pair.key.borrow() == key)?;
+        let pair = chain.remove(i);
+        if let Bucket::Tree(tree) = self {
+            if tree.map[&hash].is_empty() {
+                tree.map.remove(&hash);
+            }
+            tree.len -= 1;
+            if tree.len <= UNTREEIFY_THRESHOLD {
+                let list = std::mem::take(&mut tree.map)
+                    .into_values()
+                    .flatten()
+                    .collect();
+                *self = Bucket::List(list);
+            }
+        }
+        Some(pair)
+    }
+}
+
+impl<K, V> Bucket<K, V> {
+    // 桶中键值对的数量
+    fn len(&self) -> usize {
+        match self {
+            Bucket::List(list) => list.len(),
+            Bucket::Tree(tree) => tree.len,
+        }
+    }
+
+    // 遍历桶中的键值对
+    fn iter(&self) -> Box<dyn Iterator<Item = &Pair<K, V>> + '_> {
+        match self {
+            Bucket::List(list) => Box::new(list.iter()),
+            Bucket::Tree(tree) => Box::new(tree.map.values().flatten()),
+        }
+    }
+
+    // 取出桶中的全部键值对
+    fn into_pairs(self) -> Vec<Pair<K, V>> {
+        match self {
+            Bucket::List(list) => list,
+            Bucket::Tree(tree) => tree.map.into_values().flatten().collect(),
+        }
+    }
+}
+
+/* 链式地址哈希表 */
+pub struct HashMapChaining<K, V, S = RandomState> {
+    size: usize,
+    capacity: usize,
+    policy: ResizePolicy,
+    buckets: Vec<Bucket<K, V>>,
+    old_buckets: Vec<Bucket<K, V>>, // 渐进式扩容时尚未搬运完的旧桶数组，为空表示没有在扩容
+    rehash_idx: usize,              // 下一个待搬运的旧桶
+    rehash_step: Option<usize>,     // 每次操作至少搬运的旧桶数量，None表示一次性扩容
+    rehash_batch: usize,            // 本轮渐进式扩容每次操作实际搬运的旧桶数量
+    resizes: usize,                 // 扩容与缩容的次数
+    rehash_work: usize,             // 累计搬运的键值对数量
+    hasher: S,
+}
+
+/* 渐进式扩容期间键值对所在的桶数组 */
+#[derive(Clone, Copy)]
+enum Table {
+    Old,
+    New,
+}
+
+impl<K: Hash + Eq, V> HashMapChaining<K, V, RandomState> {
+    /* 构造方法 */
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> HashMapChaining<K, V, S> {
+    // 指定哈希算法的构造方法
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            size: 0,
+            capacity: 4,
+            policy: ResizePolicy::new(2.0 / 3.0),
+            buckets: (0..4).map(|_| Bucket::new()).collect(),
+            old_buckets: Vec::new(),
+            rehash_idx: 0,
+            rehash_step: None,
+            rehash_batch: 0,
+            resizes: 0,
+            rehash_work: 0,
+            hasher,
+        }
+    }
+
+    /* 设置扩容与缩容策略，只能在插入键值对之前调用 */
+    pub fn with_policy(mut self, policy: ResizePolicy) -> Self {
+        assert!(self.is_empty(), "只能为空哈希表设置扩容策略");
+        self.policy = policy;
+        self.capacity = policy.round_capacity(policy.min_capacity);
+        self.buckets = (0..self.capacity).map(|_| Bucket::new()).collect();
+        self
+    }
+
+    /* 开启渐进式扩容，每次操作搬运step个旧桶 */
+    pub fn incremental(mut self, step: usize) -> Self {
+        assert!(step > 0, "每次至少搬运1个桶");
+        self.rehash_step = Some(step);
+        self
+    }
+
+    /* 哈希函数：返回完整的哈希值与桶索引 */
+    fn hash_func<Q>(&self, key: &Q) -> (u64, usize)
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        (hash, (hash % self.capacity as u64) as usize)
+    }
+
+    /* 哈希值在旧桶数组中的桶索引；旧桶已经搬运过或没有在扩容时返回None */
+    fn old_index(&self, hash: u64) -> Option<usize> {
+        if self.old_buckets.is_empty() {
+            return None;
+        }
+        let index = (hash % self.old_buckets.len() as u64) as usize;
+        (index >= self.rehash_idx).then_some(index)
+    }
+
+    /* 负载因子 */
+    pub fn load_factor(&self) -> f32 {
+        self.size as f32 / self.capacity as f32
+    }
+
+    // 键值对数量
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 判断哈希表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 桶的数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 扩容与缩容策略
+    pub fn policy(&self) -> ResizePolicy {
+        self.policy
+    }
+
+    // 是否正在渐进式扩容
+    pub fn is_rehashing(&self) -> bool {
+        !self.old_buckets.is_empty()
+    }
+
+    // 累计搬运的键值对数量
+    pub fn rehash_work(&self) -> usize {
+        self.rehash_work
+    }
+
+    // 从当前状态开始，至少还能执行多少次插入或删除而不触发扩容或缩容(至少为1)
+    fn headroom(&self) -> usize {
+        let grow_limit = (self.capacity as f64 * self.policy.grow_thres).floor() as usize;
+        let grow_room = grow_limit.saturating_sub(self.size);
+        let shrink_room = if self.policy.shrink_thres > 0.0 {
+            let shrink_limit = (self.capacity as f64 * self.policy.shrink_thres).ceil() as usize;
+            self.size.saturating_sub(shrink_limit)
+        } else {
+            usize::MAX
+        };
+        grow_room.min(shrink_room).max(1)
+    }
+
+    // 已经转换为树的桶的数量
+    pub fn tree_buckets(&self) -> usize {
+        self.old_buckets
+            .iter()
+            .chain(&self.buckets)
+            .filter(|bucket| matches!(bucket, Bucket::Tree(..)))
+            .count()
+    }
+
+    // 最长的桶中键值对的数量
+    pub fn max_chain_len(&self) -> usize {
+        self.old_buckets
+            .iter()
+            .chain(&self.buckets)
+            .map(Bucket::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /* 删除操作 */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.rehash_some();
+        let (hash, table, index) = self.locate(key)?;
+        let bucket = match table {
+            Table::Old => &mut self.old_buckets[index],
+            Table::New => &mut self.buckets[index],
+        };
+
+        // 从Bucket中删除键值对
+        let pair = bucket.remove(hash, key)?;
+        self.size -= 1;
+
+        // 当负载因子低于缩容阈值时，执行缩容
+        if let Some(capacity) = self.policy.shrunk(self.size, self.capacity) {
+            self.resize(capacity);
+        }
+        Some(pair.value)
+    }
+
+    /* 预留空间：之后再插入additional个键值对都不会触发扩容 */
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.policy.capacity_for(self.size + additional);
+        if capacity > self.capacity {
+            self.resize(capacity);
+            self.rehash_buckets(usize::MAX);
+        }
+    }
+
+    /* 把容量缩小到恰好能容纳现有键值对 */
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.policy.capacity_for(self.size);
+        if capacity < self.capacity {
+            self.resize(capacity);
+            self.rehash_buckets(usize::MAX);
+        }
+    }
+
+    /* 扩容或缩容哈希表 */
+    fn resize(&mut self, capacity: usize) {
+        // 插入与删除触发扩容或缩容时，上一轮渐进式扩容一定已经结束；只有reserve与shrink_to_fit会提前触发，先把剩下的旧桶搬完
+        self.rehash_buckets(usize::MAX);
+
+        // 暂存原哈希表
+        let buckets_tmp = std::mem::take(&mut self.buckets);
+
+        // 初始化新容量的哈希表
+        self.capacity = capacity;
+        self.resizes += 1;
+        self.buckets = (0..self.capacity).map(|_| Bucket::new()).collect();
+
+        // 渐进式扩容：保留旧桶数组，之后逐步搬运，保证在下一次扩容之前搬完
+        if let Some(step) = self.rehash_step {
+            self.rehash_batch = step.max(buckets_tmp.len().div_ceil(self.headroom()));
+            self.old_buckets = buckets_tmp;
+            self.rehash_idx = 0;
+            return;
+        }
+
+        // 将键值对从原哈希表中搬运至新的哈希表，key互不相同，直接放入对应的桶即可
+        for bucket in buckets_tmp {
+            for pair in bucket.into_pairs() {
+                self.push_new(pair);
+                self.rehash_work += 1;
+            }
+        }
+    }
+
+    /* 把一个确定不存在的键值对放入新桶数组 */
+    fn push_new(&mut self, pair: Pair<K, V>) {
+        let (hash, index) = self.hash_func(&pair.key);
+        let hasher = &self.hasher;
+        self.buckets[index].push(hash, pair, |key| hasher.hash_one(key));
+    }
+
+    /* 渐进式扩容时，每次操作顺便搬运一部分旧桶 */
+    fn rehash_some(&mut self) {
+        self.rehash_buckets(self.rehash_batch);
+    }
+
+    /* 搬运至多n个旧桶，全部搬完后释放旧桶数组 */
+    fn rehash_buckets(&mut self, n: usize) {
+        if self.old_buckets.is_empty() {
+            return;
+        }
+        let end = self
+            .old_buckets
+            .len()
+            .min(self.rehash_idx.saturating_add(n));
+        for i in self.rehash_idx..end {
+            let bucket = std::mem::replace(&mut self.old_buckets[i], Bucket::new());
+            for pair in bucket.into_pairs() {
+                self.push_new(pair);
+                self.rehash_work += 1;
+            }
+        }
+        self.rehash_idx = end;
+        if self.rehash_idx == self.old_buckets.len() {
+            self.old_buckets = Vec::new();
+            self.rehash_idx = 0;
+        }
+    }
+
+    /* 添加操作：key已存在时更新value，并返回旧的value */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.rehash_some();
+
+        // 当负载因子超过阈值时，执行扩容
+        if self.policy.should_grow(self.size, self.capacity) {
+            self.resize(self.policy.grown(self.capacity));
+        }
+
+        // 若key已存在(在未搬运的旧桶或新桶中)，则更新到对应value并返回
+        if let Some(pair) = self.find_mut(&key) {
+            return Some(std::mem::replace(&mut pair.value, value));
+        }
+
+        // 如无该key，则将键值对添加到新桶中
+        self.push_new(Pair { key, value });
+        self.size += 1;
+        None
+    }
+
+    /* 找出key所在的桶数组与桶索引：先查看尚未搬运的旧桶，再查看新桶 */
+    fn locate<Q>(&self, key: &Q) -> Option<(u64, Table, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (hash, index) = self.hash_func(key);
+        if let Some(old) = self.old_index(hash) {
+            if self.old_buckets[old].find(hash, key).is_some() {
+                return Some((hash, Table::Old, old));
+            }
+        }
+        self.buckets[index]
+            .find(hash, key)
+            .map(|_| (hash, Table::New, index))
+    }
+
+    fn find_mut<Q>(&mut self, key: &Q) -> Option<&mut Pair<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (hash, table, index) = self.locate(key)?;
+        let bucket = match table {
+            Table::Old => &mut self.old_buckets[index],
+            Table::New => &mut self.buckets[index],
+        };
+        bucket.find_mut(hash, key)
+    }
+
+    /* 查询操作 */
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (hash, table, index) = self.locate(key)?;
+        let bucket = match table {
+            Table::Old => &self.old_buckets[index],
+            Table::New => &self.buckets[index],
+        };
+
+        // 在Bucket中查找key，返回对应value
+        bucket.find(hash, key).map(|pair| &pair.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.rehash_some();
+        self.find_mut(key).map(|pair| &mut pair.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V, S> HashMapChaining<K, V, S> {
+    /* 遍历所有键值对：先遍历尚未搬运的旧桶，再遍历新桶 */
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.old_buckets
+            .iter()
+            .chain(&self.buckets)
+            .flat_map(Bucket::iter)
+            .map(|pair| (&pair.key, &pair.value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /* 统计快照：直方图统计每个桶的链长。渐进式扩容期间包括尚未搬运的旧桶，
+        容量与负载因子也按实际占用的桶数(新桶数组加上尚未搬运的旧桶)计算，与直方图保持一致 */
+    pub fn stats(&self) -> MapStats {
+        let buckets = self
+            .old_buckets
+            .iter()
+            .skip(self.rehash_idx)
+            .chain(&self.buckets);
+        let histogram = MapStats::histogram_of(buckets.map(|bucket| bucket.len()));
+        let capacity = self.capacity + self.old_buckets.len().saturating_sub(self.rehash_idx);
+        MapStats {
+            size: self.size,
+            capacity,
+            load_factor: self.size as f64 / capacity as f64,
+            max_probe: histogram.len().saturating_sub(1),
+            histogram,
+            tombstones: 0,
+            resizes: self.resizes,
+            rehash_work: self.rehash_work,
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> HashMapChaining<K, V, S> {
+    /* 打印哈希表：树化的桶以tree标出 */
+    pub fn printhash(&self) {
+        let print = |prefix: &str, bucket: &Bucket<K, V>| {
+            let res: Vec<String> = bucket
+                .iter()
+                .map(|pair| format!("{:?} -> {:?}", pair.key, pair.value))
+                .collect();
+            let kind = if let Bucket::Tree(..) = bucket {
+                "tree "
+            } else {
+                ""
+            };
+            println!("{}{}{:?}", prefix, kind, res);
+        };
+        for bucket in &self.buckets {
+            print("", bucket);
+        }
+        // 渐进式扩容期间，再打印尚未搬运的旧桶
+        for bucket in self.old_buckets.iter().skip(self.rehash_idx) {
+            print("old ", bucket);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for HashMapChaining<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for HashMapChaining<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,461 @@
+/* 布谷鸟哈希(Cuckoo hashing)
+        开放寻址和链式地址的查询都要沿着探测路径或链表走下去，最坏情况下的长度没有上界。
+        布谷鸟哈希为每个key准备d个哈希函数(通常d=2)，key只能存放在这d个候选桶之一，查询时最多检查d个桶，最坏情况也是O(1)。
+            1.查找元素：依次检查d个候选桶，找不到就说明key不存在。
+            2.插入元素：若某个候选桶有空位则直接放入；否则像布谷鸟占巢一样，把其中一个候选桶里的键值对“踢”出去，
+            被踢出的键值对再去它自己的其他候选桶里找位置，如此形成一条驱逐链。
+            3.驱逐链可能形成环而永远无法结束，因此限制驱逐次数，超过上限就换一组新的种子重新哈希全部键值对。
+        两个改进：
+            1.分桶(bucketized)：每个桶有多个槽位(例如4路)，候选位置从d个增加到d*4个，负载因子可以从约50%提高到90%以上。
+            2.暂存区(stash)：驱逐失败的少量键值对先放入一个很小的暂存区，查询时额外检查它，能大幅减少重新哈希的次数。
+        查询最多检查 d * 槽位数 + 暂存区大小 个位置，这个上界与键值对数量无关。
+        哈希函数来自hash_algo中带种子的SeededState。
+        扩容与缩容由ResizePolicy决定(见resize.rs)，容量指桶的数量，负载因子则按槽位总数计算。 */
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use hash_algo::seeded::{splitmix64, SeededState};
+
+use crate::resize::ResizePolicy;
+use crate::stats::MapStats;
+use crate::Pair;
+
+/* 布谷鸟哈希表 */
+pub struct CuckooMap<K, V> {
+    size: usize,                    // 键值对数量
+    capacity: usize,                // 桶的数量
+    ways: usize,                    // 每个桶的槽位数
+    policy: Option<ResizePolicy>,   // 扩容与缩容策略，None表示按配置选择扩容阈值
+    max_kicks: usize,               // 驱逐链的长度上限
+    slots: Vec<Option<Pair<K, V>>>, // 槽位数组，第i个桶占据[i*ways, (i+1)*ways)
+    stash: Vec<Pair<K, V>>,         // 暂存区
+    stash_capacity: usize,          // 暂存区容量，为0表示不使用暂存区
+    hashers: Vec<SeededState>,      // d个带种子的哈希函数
+    rng: u64,                       // 生成新种子与选择驱逐对象的随机数状态
+    rehash_count: usize,            // 重新哈希(包括扩容与缩容)的次数
+    rehash_work: usize,             // 重新哈希时累计放置的键值对数量
+}
+
+/* 键值对的位置 */
+#[derive(Clone, Copy)]
+enum Location {
+    Slot(usize),  // 槽位数组中的下标
+    Stash(usize), // 暂存区中的下标
+}
+
+impl<K: Hash + Eq, V> CuckooMap<K, V> {
+    /* 构造方法：2个哈希函数、单槽位、无暂存区，种子随机 */
+    pub fn new() -> Self {
+        Self::with_seed(RandomState::new().hash_one(0u64))
+    }
+
+    // 指定初始种子的构造方法，相同的种子总是得到相同的行为
+    pub fn with_seed(seed: u64) -> Self {
+        let mut map = Self {
+            size: 0,
+            capacity: 4,
+            ways: 1,
+            policy: None,
+            max_kicks: 0,
+            slots: Vec::new(),
+            stash: Vec::new(),
+            stash_capacity: 0,
+            hashers: Vec::new(),
+            rng: seed,
+            rehash_count: 0,
+            rehash_work: 0,
+        };
+        map.configure(2, 1);
+        map
+    }
+
+    /* 设置哈希函数的个数d */
+    pub fn with_hashes(mut self, d: usize) -> Self {
+        assert!(d >= 2, "至少需要2个哈希函数");
+        assert!(self.is_empty(), "只能在插入元素之前设置");
+        self.configure(d, self.ways);
+        self
+    }
+
+    /* 设置每个桶的槽位数，4路分桶可以使负载因子达到90%以上 */
+    pub fn with_ways(mut self, ways: usize) -> Self {
+        assert!(ways >= 1, "每个桶至少有1个槽位");
+        assert!(self.is_empty(), "只能在插入元素之前设置");
+        self.configure(self.hashers.len(), ways);
+        self
+    }
+
+    /* 设置暂存区容量 */
+    pub fn with_stash(mut self, stash_capacity: usize) -> Self {
+        self.stash_capacity = stash_capacity;
+        self
+    }
+
+    /* 设置负载因子阈值，其余参数取默认值 */
+    pub fn with_load_thres(self, load_thres: f64) -> Self {
+        self.with_policy(ResizePolicy::new(load_thres))
+    }
+
+    /* 设置扩容与缩容策略，只能在插入元素之前调用 */
+    pub fn with_policy(mut self, policy: ResizePolicy) -> Self {
+        assert!(self.is_empty(), "只能在插入元素之前设置");
+        assert!(policy.grow_thres < 1.0, "扩容阈值必须小于1");
+        self.policy = Some(policy);
+        self.capacity = policy.round_capacity(policy.min_capacity);
+        self.configure(self.hashers.len(), self.ways);
+        self
+    }
+
+    // 根据哈希函数个数与槽位数重建空表
+    fn configure(&mut self, d: usize, ways: usize) {
+        self.ways = ways;
+        self.max_kicks = 100 * d * ways;
+        self.hashers = SeededState::family(splitmix64(&mut self.rng), d);
+        self.slots = (0..self.capacity * ways).map(|_| None).collect();
+    }
+
+    /* 扩容与缩容策略：未指定时扩容阈值使用经验上可以达到的值 */
+    pub fn policy(&self) -> ResizePolicy {
+        self.policy.unwrap_or_else(|| {
+            ResizePolicy::new(match (self.hashers.len(), self.ways) {
+                (2, 1) => 0.45,
+                (_, 1) => 0.85,
+                _ => 0.9,
+            })
+        })
+    }
+
+    /* 负载因子：键值对数量(包括暂存区)与槽位总数之比 */
+    pub fn load_factor(&self) -> f64 {
+        self.size as f64 / self.slots.len() as f64
+    }
+
+    // 键值对数量
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 判断哈希表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 桶的数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 暂存区中的键值对数量
+    pub fn stash_len(&self) -> usize {
+        self.stash.len()
+    }
+
+    // 重新哈希(包括扩容)的次数
+    pub fn rehash_count(&self) -> usize {
+        self.rehash_count
+    }
+
+    /* 一次查询最多检查的位置数，与键值对数量无关 */
+    pub fn lookup_bound(&self) -> usize {
+        self.hashers.len() * self.ways + self.stash_capacity
+    }
+
+    /* 第i个哈希函数给出的桶对应的槽位范围 */
+    fn bucket<Q>(&self, i: usize, key: &Q) -> std::ops::Range<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        let b = (self.hashers[i].hash_one(key) % self.capacity as u64) as usize;
+        b * self.ways..(b + 1) * self.ways
+    }
+
+    /* 搜索key所在的位置 */
+    fn find<Q>(&self, key: &Q) -> Option<Location>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // 依次检查d个候选桶的每个槽位
+        for i in 0..self.hashers.len() {
+            for slot in self.bucket(i, key) {
+                if matches!(&self.slots[slot], Some(pair) if pair.key.borrow() == key) {
+                    return Some(Location::Slot(slot));
+                }
+            }
+        }
+        // 最后检查暂存区
+        self.stash
+            .iter()
+            .position(|pair| pair.key.borrow() == key)
+            .map(Location::Stash)
+    }
+
+    // 取出某个位置上的键值对的引用
+    fn pair_mut(&mut self, location: Location) -> &mut Pair<K, V> {
+        match location {
+            Location::Slot(slot) => self.slots[slot].as_mut().expect("找到的槽位中有键值对"),
+            Location::Stash(i) => &mut self.stash[i],
+        }
+    }
+
+    /* 查询操作 */
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.find(key)? {
+            Location::Slot(slot) => self.slots[slot].as_ref().map(|pair| &pair.value),
+            Location::Stash(i) => Some(&self.stash[i].value),
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.find(key)?;
+        Some(&mut self.pair_mut(location).value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /* 添加操作：key已存在时覆盖value，并返回旧的value */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(location) = self.find(&key) {
+            return Some(std::mem::replace(&mut self.pair_mut(location).value, value));
+        }
+        // 当负载因子超过阈值时，执行扩容
+        let policy = self.policy();
+        if policy.should_grow(self.size + 1, self.slots.len()) {
+            self.rehash(policy.grown(self.capacity), None);
+        }
+        self.size += 1;
+        if let Err(homeless) = self.place(Pair { key, value }, self.max_kicks) {
+            // 驱逐链过长，先尝试放入暂存区，暂存区也满了就换种子重新哈希
+            if self.stash.len() < self.stash_capacity {
+                self.stash.push(homeless);
+            } else {
+                self.rehash(self.capacity, Some(homeless));
+            }
+        }
+        None
+    }
+
+    /* 把键值对放入它的某个候选桶，必要时沿驱逐链踢出其他键值对
+    成功返回Ok；驱逐次数超过上限时，返回此刻手中无处安放的键值对(不一定是最初插入的那个) */
+    fn place(&mut self, mut pair: Pair<K, V>, max_kicks: usize) -> Result<(), Pair<K, V>> {
+        for _ in 0..=max_kicks {
+            // 若某个候选桶有空槽位，直接放入
+            for i in 0..self.hashers.len() {
+                let bucket = self.bucket(i, &pair.key);
+                if let Some(slot) = bucket.into_iter().find(|&s| self.slots[s].is_none()) {
+                    self.slots[slot] = Some(pair);
+                    return Ok(());
+                }
+            }
+            if max_kicks == 0 {
+                break;
+            }
+            // 候选桶都满了，随机踢出一个候选槽位中的键值对，拿着它继续寻找位置
+            let r = splitmix64(&mut self.rng) as usize;
+            let bucket = self.bucket(r % self.hashers.len(), &pair.key);
+            let slot = bucket.start + (r >> 32) % self.ways;
+            pair = self.slots[slot]
+                .replace(pair)
+                .expect("满的桶中每个槽位都有键值对");
+        }
+        Err(pair)
+    }
+
+    /* 换一组新的种子，按给定的桶数量重新放置全部键值对；连续失败时扩容 */
+    fn rehash(&mut self, mut capacity: usize, extra: Option<Pair<K, V>>) {
+        let mut pending: Vec<Pair<K, V>> = std::mem::take(&mut self.slots)
+            .into_iter()
+            .flatten()
+            .chain(std::mem::take(&mut self.stash))
+            .chain(extra)
+            .collect();
+        let mut attempts = 0;
+        loop {
+            self.rehash_count += 1;
+            self.rehash_work += pending.len();
+            self.capacity = capacity;
+            self.hashers = SeededState::family(splitmix64(&mut self.rng), self.hashers.len());
+            self.slots = (0..self.capacity * self.ways).map(|_| None).collect();
+            let mut failed = None;
+            while let Some(pair) = pending.pop() {
+                if let Err(homeless) = self.place(pair, self.max_kicks) {
+                    if self.stash.len() < self.stash_capacity {
+                        self.stash.push(homeless);
+                    } else {
+                        failed = Some(homeless);
+                        break;
+                    }
+                }
+            }
+            let Some(homeless) = failed else {
+                return;
+            };
+            // 本轮失败：收回已放置的键值对，换种子重试，连续3次失败说明表太满了，扩容
+            pending.extend(std::mem::take(&mut self.slots).into_iter().flatten());
+            pending.append(&mut self.stash);
+            pending.push(homeless);
+            attempts += 1;
+            if attempts % 3 == 0 {
+                capacity = self.policy().grown(capacity);
+            }
+        }
+    }
+
+    /* 查询key时需要检查的位置数：按候选桶的顺序逐个检查槽位，最后检查暂存区 */
+    fn probe_length(&self, location: Location, key: &K) -> usize {
+        let slots_checked = self.hashers.len() * self.ways;
+        match location {
+            Location::Slot(slot) => (0..self.hashers.len())
+                .find_map(|i| {
+                    let bucket = self.bucket(i, key);
+                    bucket
+                        .contains(&slot)
+                        .then(|| i * self.ways + slot - bucket.start + 1)
+                })
+                .expect("键值对在它的某个候选桶中"),
+            Location::Stash(i) => slots_checked + i + 1,
+        }
+    }
+
+    /* 统计快照：直方图统计每个键值对的探测长度 */
+    pub fn stats(&self) -> MapStats {
+        let slots = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, pair)| Some((Location::Slot(slot), pair.as_ref()?)));
+        let stash = self
+            .stash
+            .iter()
+            .enumerate()
+            .map(|(i, pair)| (Location::Stash(i), pair));
+        let histogram = MapStats::histogram_of(
+            slots
+                .chain(stash)
+                .map(|(location, pair)| self.probe_length(location, &pair.key)),
+        );
+        MapStats {
+            size: self.size,
+            capacity: self.capacity,
+            load_factor: self.load_factor(),
+            max_probe: histogram.len().saturating_sub(1),
+            histogram,
+            tombstones: 0,
+            resizes: self.rehash_count,
+            rehash_work: self.rehash_work,
+        }
+    }
+
+    /* 删除操作：返回被删除的value */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let pair = match self.find(key)? {
+            Location::Slot(slot) => self.slots[slot].take().expect("找到的槽位中有键值对"),
+            Location::Stash(i) => self.stash.swap_remove(i),
+        };
+        self.size -= 1;
+        // 腾出了槽位，暂存区中的键值对可能可以直接放回表中
+        for pair in std::mem::take(&mut self.stash) {
+            if let Err(pair) = self.place(pair, 0) {
+                self.stash.push(pair);
+            }
+        }
+        // 当负载因子低于缩容阈值时，执行缩容；策略按桶计算，因此把键值对数量折算为每个桶一个
+        let buckets_needed = self.size.div_ceil(self.ways);
+        if let Some(capacity) = self.policy().shrunk(buckets_needed, self.capacity) {
+            self.rehash(capacity, None);
+        }
+        Some(pair.value)
+    }
+
+    /* 预留空间：之后再插入additional个键值对都不会触发扩容 */
+    pub fn reserve(&mut self, additional: usize) {
+        let buckets_needed = (self.size + additional).div_ceil(self.ways);
+        let capacity = self.policy().capacity_for(buckets_needed);
+        if capacity > self.capacity {
+            self.rehash(capacity, None);
+        }
+    }
+
+    /* 把容量缩小到恰好能容纳现有键值对 */
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.policy().capacity_for(self.size.div_ceil(self.ways));
+        if capacity < self.capacity {
+            self.rehash(capacity, None);
+        }
+    }
+}
+
+impl<K, V> CuckooMap<K, V> {
+    /* 遍历所有键值对 */
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots
+            .iter()
+            .flatten()
+            .chain(&self.stash)
+            .map(|pair| (&pair.key, &pair.value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> CuckooMap<K, V> {
+    /* 打印哈希表：每行一个桶，最后是暂存区 */
+    pub fn print(&self) {
+        for bucket in self.slots.chunks(self.ways) {
+            let res: Vec<String> = bucket
+                .iter()
+                .map(|slot| match slot {
+                    Some(pair) => format!("{:?} -> {:?}", pair.key, pair.value),
+                    None => "NULL".to_string(),
+                })
+                .collect();
+            println!("{:?}", res);
+        }
+        let stash: Vec<String> = self
+            .stash
+            .iter()
+            .map(|pair| format!("{:?} -> {:?}", pair.key, pair.value))
+            .collect();
+        println!("stash: {:?}", stash);
+    }
+}
+
+impl<K: Hash + Eq, V> Default for CuckooMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for CuckooMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,16 @@
+/* 哈希冲突的解决方案 */
+pub mod chaining;
+pub mod cuckoo;
+pub mod open_addressing;
+pub mod probe;
+pub mod resize;
+pub mod robin_hood;
+pub mod stats;
+pub mod swiss;
+
+/* 键值对 */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair<K, V> {
+    pub key: K,
+    pub value: V,
+}
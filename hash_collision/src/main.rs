@@ -2,318 +2,768 @@
     1.改良哈希表数据结构，使得哈希表可以在出现哈希冲突的时候能正常工作。
     2.仅在必要的时候，即在哈希冲突比较严重的时候，才执行扩容操作。 */
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::time::{Duration, Instant};
 
-/* 1.链式地址
-        在原始哈希表中，每个桶仅能存储一个键值对。链式地址(seperate chaining)将单个元素转换为链表，将键值对作为链表节点，将所有发生冲突的键值对都存储在一个链表中。
-        
-        基于链式地址实现的哈希表的操作方法发生了如下变化：
-            1.查询元素：输入key，经过哈希函数得到桶索引，即可访问链表头节点，然后遍历链表并对比key以查找目标键值对。
-            2.添加元素：首先通过哈希函数访问链表头节点，然后将节点(键值对)添加到链表中。
-            3.根据哈希函数的结果访问链表头部，接着遍历链表以查找目标节点并将其删除。
-            
-        链式地址存在以下局限性：
-            1.占用空间增大：链表需要包含节点指针，它相比数组更加耗费内存空间。
-            2.查询效率降低：因为需要线性遍历链表来查找对应元素。
-            
-        以下给出了链式地址哈希表的简单实现，需要注意两点
-            1.使用列表(动态数组)代替链表，从而简化代码。在这种设定下，哈希表(数组)包含多个bucket，每个bucket都是一个列表。
-            2.以下实现包含哈希表扩容方法。当负载因子超过2/3时，我们将哈希表扩容至原先的2倍。 */
-#[derive(Debug, Clone, PartialEq)]
-pub struct Pair{
-    key: i32,
-    value: String,
-}
+use hash_algo::seeded::splitmix64;
+use hash_collision::chaining::HashMapChaining;
+use hash_collision::cuckoo::CuckooMap;
+use hash_collision::open_addressing::OpenAddressingMap;
+use hash_collision::probe::{DoubleHashing, LinearProbing, ProbeStrategy, QuadraticProbing};
+use hash_collision::resize::{CapacityKind, ResizePolicy};
+use hash_collision::robin_hood::RobinHoodMap;
+use hash_collision::stats::{stats_csv, stats_json, MapStats};
+use hash_collision::swiss::SwissMap;
 
-/* 链式地址哈希表 */
-struct HashMapChaining{
-    size: usize,
-    capacity: usize,
-    load_thres: f32,
-    extend_ratio: usize,
-    buckets: Vec<Vec<Pair>>,
-}
+/* 1.链式地址 */
+// 见 chaining.rs
+
+/* 2.开放寻址 */
+// 见 open_addressing.rs
+
+/* 平方探测 */
+// 见 probe.rs
+
+/* 多次哈希 */
+// 见 probe.rs
+
+/* 罗宾汉哈希 */
+// 见 robin_hood.rs
 
-#[allow(dead_code)]
-impl HashMapChaining {
-    /* 构造方法 */
-    pub fn new() -> Self {
-        Self { size: 0, capacity: 4, load_thres: 2.0/3.0, extend_ratio: 2, buckets: vec![vec![]; 4] }
+/* 布谷鸟哈希 */
+// 见 cuckoo.rs
+
+/* SwissTable */
+// 见 swiss.rs
+
+/* 扩容与缩容策略 */
+// 见 resize.rs
+
+/* 统计信息 */
+// 见 stats.rs
+
+fn main() {
+    let mut hash_map: HashMap<i32, String> = HashMap::new();
+    hash_map.insert(1, "wudi".to_string());
+    println!("{:?}", hash_map);
+
+    /* 链式地址哈希表 */
+    let mut chaining = HashMapChaining::new();
+    chaining.insert(-1, "负一".to_string());
+    chaining.insert(12836, "小哈".to_string());
+    chaining.insert(15937, "小锣".to_string());
+    println!("{:?}", chaining.insert(-1, "小算".to_string()));
+    chaining.printhash();
+
+    /* 开放寻址哈希表 */
+    let mut open = OpenAddressingMap::new();
+    open.insert(-1, "负一".to_string());
+    open.insert(12836, "小哈".to_string());
+    println!("{:?}", open.remove(&12836));
+    open.print();
+
+    /* 罗宾汉哈希表 */
+    let mut robin = RobinHoodMap::new();
+    for key in [3, 7, 11, 15, 19] {
+        robin.insert(key, key * 10);
     }
+    robin.remove(&7);
+    robin.print();
 
-    /* 哈希函数 */
-    fn hash_func(&self, key: i32) -> usize{
-        key as usize % self.capacity as usize
+    /* 布谷鸟哈希表 */
+    let mut cuckoo = CuckooMap::new().with_ways(2).with_stash(1);
+    for key in 0..6 {
+        cuckoo.insert(key, key * key);
     }
+    cuckoo.print();
 
-    /* 负载因子 */
-    fn load_factor(&self) -> f32 {
-        self.size as f32 / self.capacity as f32
+    /* SwissTable风格的哈希表 */
+    let mut swiss = SwissMap::new();
+    for key in 0..10 {
+        swiss.insert(key, key * 100);
     }
+    swiss.remove(&3);
+    swiss.print();
+
+    check_chaining();
+    check_incremental_rehash();
+    check_treeify();
+    check_open_addressing();
+    check_tombstones();
+    check_probe_strategies();
+    check_robin_hood();
+    check_cuckoo();
+    check_swiss();
+    check_resize_policy();
+    check_stats();
+}
 
-    /* 删除操作 */
-    fn remove(&mut self, key: i32) -> Option<String> {
-        let index = self.hash_func(key);
-        let bucket = &mut self.buckets[index];
+type Fixed = BuildHasherDefault<DefaultHasher>;
 
-        // 遍历Bucket，从中删除键值对
-        for i in 0..bucket.len() {
-            if bucket[i].key == key{
-                let pair = bucket.remove(i);
-                self.size -= 1;
-                return Some(pair.value);
+/* n个落在[-modulo/2, modulo/2]内的伪随机key，由hash_algo的SplitMix64生成，种子相同时序列相同 */
+fn random_keys(mut seed: u64, n: usize, modulo: i64) -> Vec<i64> {
+    (0..n)
+        .map(|_| (splitmix64(&mut seed) >> 1) as i64 % modulo - modulo / 2)
+        .collect()
+}
+
+/* 用一串随机的插入、删除、查询同时驱动哈希表和标准库的HashMap，两者的返回值应当完全一致。key包含负数 */
+macro_rules! model_check {
+    ($map:expr) => {{
+        let mut map = $map;
+        let mut model: HashMap<i64, String> = HashMap::new();
+        for (i, key) in random_keys(42, 20_000, 2000).into_iter().enumerate() {
+            match i % 3 {
+                0 | 1 => assert_eq!(
+                    map.insert(key, i.to_string()),
+                    model.insert(key, i.to_string())
+                ),
+                _ => assert_eq!(map.remove(&key), model.remove(&key)),
             }
+            assert_eq!(map.get(&key), model.get(&key));
+            assert_eq!(map.size(), model.len());
+        }
+        for key in -1000..1000 {
+            assert_eq!(map.get(&key), model.get(&key));
+            assert_eq!(map.contains_key(&key), model.contains_key(&key));
         }
+        let mut keys: Vec<i64> = map.keys().copied().collect();
+        keys.sort();
+        let mut expected: Vec<i64> = model.keys().copied().collect();
+        expected.sort();
+        assert_eq!(keys, expected);
+        assert!(map.iter().all(|(k, v)| model.get(k) == Some(v)));
+        assert_eq!(map.values().count(), model.len());
+        map
+    }};
+}
+
+fn check_chaining() {
+    model_check!(HashMapChaining::new());
+    model_check!(HashMapChaining::new().incremental(1));
+    model_check!(HashMapChaining::with_hasher(Fixed::default()).incremental(4));
+    let map = model_check!(HashMapChaining::with_hasher(Fixed::default()));
+    assert!(map.load_factor() <= 2.0 / 3.0 * 2.0);
+
+    // String键可以用&str查询，get_mut可以原地修改
+    let mut names: HashMapChaining<String, i32> = HashMapChaining::new();
+    names.insert("小哈".to_string(), 1);
+    *names.get_mut("小哈").unwrap() += 1;
+    assert_eq!(names.get("小哈"), Some(&2));
+    assert_eq!(names.remove("小哈"), Some(2));
+    assert!(names.is_empty());
+    println!("HashMapChaining与HashMap的行为一致");
+}
+
+/* 每次插入的耗时直方图：第i格统计耗时在[2^i, 2^(i+1))微秒内的插入次数，返回直方图与最长的一次停顿 */
+fn insert_latencies(mut map: HashMapChaining<u64, u64, Fixed>, n: u64) -> (Vec<usize>, Duration) {
+    let mut histogram = vec![0; 32];
+    let mut max = Duration::ZERO;
+    for key in 0..n {
+        let start = Instant::now();
+        map.insert(key, key);
+        let elapsed = start.elapsed();
+        histogram[(64 - (elapsed.as_micros() as u64).leading_zeros()) as usize] += 1;
+        max = max.max(elapsed);
+    }
+    assert!((0..n).all(|key| map.get(&key) == Some(&key)));
+    histogram.truncate(histogram.iter().rposition(|&count| count > 0).unwrap() + 1);
+    (histogram, max)
+}
 
-        /* 若未找到，从中删除键值对 */
-        None
+/* 逐个插入n个key，再删除到只剩16个，返回单次操作搬运的键值对数量的最大值 */
+fn max_rehash_work(mut map: HashMapChaining<u64, u64, Fixed>, n: u64) -> usize {
+    let mut max = 0;
+    let mut last = map.rehash_work();
+    let mut record = |map: &HashMapChaining<u64, u64, Fixed>| {
+        max = max.max(map.rehash_work() - last);
+        last = map.rehash_work();
+    };
+    for key in 0..n {
+        map.insert(key, key);
+        record(&map);
+    }
+    for key in 16..n {
+        map.remove(&key);
+        record(&map);
     }
+    assert!((0..16).all(|key| map.get(&key) == Some(&key)));
+    max
+}
 
-    /* 扩容哈希表 */
-    fn extend(&mut self) {
-        // 暂存原哈希表
-        let buckets_tmp = std::mem::replace(&mut self.buckets, vec![]);
+/* 一次性扩容时最后一次扩容要搬运十几万个键值对；渐进式扩容每次只搬运几个桶，单次操作的搬运量有与表大小无关的上界。
+    耗时直方图只作展示：计时会受到调度的干扰，因此各运行三次，取较小的最长停顿。 */
+fn check_incremental_rehash() {
+    let n = 1 << 17;
+    let runs = |incremental: bool| {
+        (0..3)
+            .map(|_| {
+                let map = HashMapChaining::with_hasher(Fixed::default());
+                let map = if incremental { map.incremental(4) } else { map };
+                insert_latencies(map, n)
+            })
+            .min_by_key(|(_, max)| *max)
+            .unwrap()
+    };
+    let (full, full_max) = runs(false);
+    let (incremental, incremental_max) = runs(true);
+    println!("插入耗时直方图(第i格为[2^i, 2^(i+1))微秒，第0格为不足1微秒)");
+    println!("一次性扩容 {:?} 最长 {:?}", full, full_max);
+    println!("渐进式扩容 {:?} 最长 {:?}", incremental, incremental_max);
+
+    // 搬运量是确定的：一次性扩容的最后一次要搬运一半以上的键值对，渐进式扩容每次操作只搬运几个桶
+    let full_work = max_rehash_work(HashMapChaining::with_hasher(Fixed::default()), n);
+    assert!(full_work as u64 > n / 2);
+    for step in [1, 4] {
+        let map = HashMapChaining::with_hasher(Fixed::default()).incremental(step);
+        let max = max_rehash_work(map, n);
+        println!(
+            "step = {}，单次操作最多搬运 {} 个键值对(一次性扩容 {} 个)",
+            step, max, full_work
+        );
+        assert!(max <= 16 * step);
+    }
 
-        // 初始化扩容后的新哈希表
-        self.capacity *= self.extend_ratio;
-        self.buckets = vec![Vec::new(); self.capacity as usize];
-        self.size = 0;
-        
-        // 将键值对从原哈希表中搬运至新的哈希表
-        for bucket in buckets_tmp{
-            for pair in bucket{
-                self.put(pair.key, pair.value);
-            }
-        }
+    // 扩容期间的查询、覆盖与删除同时检查两个桶数组
+    let mut map = HashMapChaining::with_hasher(Fixed::default()).incremental(1);
+    let mut key = 0u64;
+    while !map.is_rehashing() {
+        map.insert(key, key);
+        key += 1;
     }
+    assert!((0..key).all(|k| map.get(&k) == Some(&k)));
+    assert_eq!(map.insert(0, 100), Some(0));
+    assert_eq!(map.remove(&1), Some(1));
+    assert_eq!(map.get(&0), Some(&100));
+    assert_eq!(map.size() as u64, key - 1);
+    assert_eq!(map.iter().count() as u64, key - 1);
+}
 
-    /* 打印哈希表 */
-    fn printhash(&self){
-        for bucket in &self.buckets{
-            let mut res = Vec::new();
-            for pair in bucket{
-                res.push(format!("{} -> {}", pair.key, pair.value));
-            }
-            println!("{:?}", res);
+fn check_open_addressing() {
+    model_check!(OpenAddressingMap::new());
+    let map = model_check!(OpenAddressingMap::with_hasher(Fixed::default()));
+    assert!(map.load_factor() <= 2.0 / 3.0);
+
+    // key为-1的键值对与删除标记互不干扰
+    let mut map: OpenAddressingMap<i32, String> = OpenAddressingMap::new();
+    map.insert(-1, "-1".to_string());
+    map.insert(7, "7".to_string());
+    assert_eq!(map.remove(&7), Some("7".to_string()));
+    assert_eq!(map.get(&-1), Some(&"-1".to_string()));
+    assert_eq!(map.get(&7), None);
+    *map.get_mut(&-1).unwrap() = "负一".to_string();
+    assert_eq!(map.remove(&-1), Some("负一".to_string()));
+    assert!(map.is_empty());
+    println!("OpenAddressingMap与HashMap的行为一致");
+}
+/* 在整个i64范围内混合插入和删除：key可以是-1、0、i64::MIN等任意值，删除标记不能越积越多 */
+fn check_tombstones() {
+    let mut map = OpenAddressingMap::with_hasher(Fixed::default());
+    let mut model: HashMap<i64, u64> = HashMap::new();
+    let mut state = 7u64;
+    let mut pool = vec![i64::MIN, i64::MAX, -1, 0, 1];
+    for i in 0..200_000u64 {
+        let seed = splitmix64(&mut state);
+        // 一半的操作使用新的随机key，另一半复用已出现过的key，保证删除能命中
+        let key = if seed & 1 == 0 {
+            let key = seed as i64;
+            pool.push(key);
+            key
+        } else {
+            pool[(seed >> 33) as usize % pool.len()]
+        };
+        // 前半程插入多于删除，后半程删除多于插入
+        let insert_ratio = if i < 100_000 { 6 } else { 3 };
+        if (seed >> 40) % 10 < insert_ratio {
+            assert_eq!(map.insert(key, i), model.insert(key, i));
+        } else {
+            assert_eq!(map.remove(&key), model.remove(&key));
         }
+        assert_eq!(map.size(), model.len());
+        assert!(map.tombstones() * 2 <= map.capacity());
     }
+    for &key in &pool {
+        assert_eq!(map.get(&key), model.get(&key));
+    }
+    assert!(map.iter().all(|(k, v)| model.get(k) == Some(v)));
 
-    /* 添加操作 */
-    fn put(&mut self, key: i32, value: String) {
-        // 当负载因子超过阈值时，执行扩容
-        if self.load_factor() > self.load_thres{
-            self.extend();
+    // 反复插满再删空：容量保持不变，删除标记会被原地重建清除
+    let mut map = OpenAddressingMap::with_hasher(Fixed::default());
+    for key in 0..1000i64 {
+        map.insert(key, key);
+    }
+    let capacity = map.capacity();
+    for round in 1..50i64 {
+        for key in 0..1000i64 {
+            assert_eq!(map.remove(&key), Some(key + (round - 1) * 1000));
         }
-
-        let index = self.hash_func(key);
-        let bucket = &mut self.buckets[index];
-
-        // 遍历Bucket,若遇到指定key，则更新到对应value并返回
-        for pair in bucket{
-            if pair.key == key{
-                pair.value = value;
-                return;
-            }
+        assert!(map.is_empty());
+        for key in 0..1000i64 {
+            assert_eq!(map.insert(key, key + round * 1000), None);
+        }
+        assert_eq!(map.capacity(), capacity);
+        // 经过多轮删除后，查询不存在的key仍然能正常结束
+        assert_eq!(map.get(&-1), None);
     }
-    let bucket = &mut self.buckets[index];
-
-    // 如无该key，则将键值对添加到尾部
-    let pair = Pair{key, value: value.clone()};
-    bucket.push(pair);
-    self.size += 1;
+    println!("删除标记不会拖垮开放寻址哈希表");
 }
 
-    /* 查询操作 */
-    fn get(&self, key: i32) -> Option<&str>{
-        let index = self.hash_func(key);
-        let bucket = &self.buckets[index];
-
-        // 遍历Bucket，若找到key，则返回对应value
-        for pair in bucket{
-            if pair.key == key{
-                return Some(&pair.value);
+/* 容量为2的幂时，每种探测策略的前capacity次探测都应当恰好访问每个桶一次 */
+fn covers_every_bucket(strategy: &impl ProbeStrategy) -> bool {
+    (2..=12).all(|bits| {
+        let capacity = 1usize << bits;
+        random_keys(bits, 50, i64::MAX).into_iter().all(|hash| {
+            let mut seen = vec![false; capacity];
+            for i in 0..capacity {
+                seen[strategy.index(hash as u64, i, capacity)] = true;
             }
-        }
+            seen.iter().all(|&s| s)
+        })
+    })
+}
 
-        // 若没有找到key，则返回None
-        None
+fn check_probe_strategies() {
+    assert!(covers_every_bucket(&LinearProbing));
+    assert!(covers_every_bucket(&QuadraticProbing));
+    assert!(covers_every_bucket(&DoubleHashing));
+
+    model_check!(OpenAddressingMap::with_hasher_and_strategy(
+        Fixed::default(),
+        QuadraticProbing
+    ));
+    model_check!(OpenAddressingMap::with_hasher_and_strategy(
+        Fixed::default(),
+        DoubleHashing
+    ));
+    model_check!(OpenAddressingMap::with_strategy(DoubleHashing).with_load_thres(0.95));
+
+    // 负载因子很高时哈希表几乎没有空桶，查询不存在的key也必须能结束，且探测次数不超过容量
+    let mut map = OpenAddressingMap::with_strategy(QuadraticProbing).with_load_thres(0.99);
+    for key in 0..1000 {
+        map.insert(key, key);
+    }
+    assert!(map.load_factor() > 0.9);
+    for key in 0..1000 {
+        assert_eq!(map.get(&key), Some(&key));
+        assert!(map.probe_count(&key) >= 1);
     }
+    assert_eq!(map.get(&-1), None);
+    assert!(map.probe_count(&-1) <= map.capacity());
+    println!("线性探测、平方探测与双重哈希都能访问每个桶");
 }
 
-/* 2.开放寻址
-        开放寻址不引入额外的数据结构，而是通过“多次探测”来处理哈希冲突，探测方式主要包含线性探测、平方探测和多次哈希等。
-        1.线性探测
-        线性探测采用固定步长的线性搜索来进行探测，其操作方法和普通哈希表有所不同。
-            1.插入元素：通过哈希函数计算bucket索引，若发现bucket内已有元素，则从冲突位置向后线性遍历(步长通常为1),知道找到空bucket，将元素插入其中。
-            2.查找元素：若发现哈希冲突，则使用相同步长向后进行线性遍历，知道找到对应的元素，返回value即可；如果遇到空bucket，说明元素不在哈希表中，返回None。
-        线性检测容易产生“聚集现象”。
-        我们不能在开放寻址哈希表中直接删除元素。这是因为删除元素会在数组内产生一个空桶None，而当查询元素时，线性探测到该空bucket就会返回，因此在该空bucket之下的元素都无法再被访问到，程序可能会误判这些元素不存在。
-        为了解决该问题，我们可以采用懒删除(lazy deletion)机制：它不直接从哈希表中移除元素，而是利用一个常量TOMBSTONE来标记这个桶。在该机制下，
-        None和TOMBSTONE都代表空桶，都可以放置键值对。但是不同的是，线性检测到TOMBSTONE时应该继续遍历，因为在其下还可能存在键值对。
-        然而，懒删除可能会加速哈希表的性能退化。这是因为在每一次删除操作时都会产生一个删除标记，随着TOMBSTONE的增加，搜索时间也会增加，因为线性检测可能需要跳过多个TOMBSTONE才能找到目标元素。
-        为此，可以考虑在线性探测中记录首个TOMBSTONE的索引，并将搜索到的目标元素与该TOMBSTONE交换位置。这样做的好处是每当查询或者添加元素的时候，元素会被移动至距离理想位置(探测起始点)更近的桶，从而优化查询效率。
-         */
-
-/* 开放寻址哈希表 */
-#[allow(dead_code)]
-struct HashMAPOpenAdressing{
-    size: usize,    // 键值对数量
-    capacity: usize, // 哈希表容量
-    load_thres: f64, // 触发扩容的负载因子阈值
-    extend_ratio: usize, // 扩容倍数
-    buckets: Vec<Option<Pair>>, // 桶数组
-    tombstone: Option<Pair>, // 删除标记
+fn check_robin_hood() {
+    model_check!(RobinHoodMap::new());
+    model_check!(RobinHoodMap::with_hasher(Fixed::default()).with_load_thres(0.95));
+
+    // 相同的key、相同的负载因子下，与线性探测比较探测长度
+    let keys = random_keys(99, 50_000, i64::MAX);
+    let mut robin = RobinHoodMap::with_hasher(Fixed::default()).with_load_thres(0.9);
+    let mut linear = OpenAddressingMap::with_hasher(Fixed::default()).with_load_thres(0.9);
+    for &key in &keys {
+        robin.insert(key, ());
+        linear.insert(key, ());
+    }
+    assert_eq!(robin.capacity(), linear.capacity());
+    let lengths: Vec<f64> = linear
+        .keys()
+        .map(|key| linear.probe_count(key) as f64)
+        .collect();
+    let mean = lengths.iter().sum::<f64>() / lengths.len() as f64;
+    let variance =
+        lengths.iter().map(|len| (len - mean).powi(2)).sum::<f64>() / lengths.len() as f64;
+    let max = lengths.iter().cloned().fold(0.0, f64::max) as usize;
+    // 交换键值对不改变探测长度的总和，因此平均值相同，但方差与最大值小得多
+    assert!((robin.mean_probe_length() - mean).abs() < 1e-9);
+    assert!(robin.probe_length_variance() * 4.0 < variance);
+    let robin_max = robin.max_probe_length();
+    assert!(robin_max < max);
+    assert!(robin.keys().all(|key| robin.probe_count(key) <= robin_max));
+
+    // 查询不存在的key时可以提前结束
+    let misses = 1..=10_000i64;
+    let robin_misses: usize = misses.clone().map(|key| robin.probe_count(&-key)).sum();
+    let linear_misses: usize = misses.map(|key| linear.probe_count(&-key)).sum();
+    assert!(robin_misses < linear_misses);
+
+    // 后移删除不留下删除标记：删掉一半后剩下的key探测长度不会变长，已删除的key查不到
+    let max_before = robin.max_probe_length();
+    for key in keys.iter().step_by(2) {
+        assert_eq!(robin.remove(key), Some(()));
+    }
+    assert!(robin.max_probe_length() <= max_before);
+    assert!(keys
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .all(|key| robin.contains_key(key)));
+    assert!(keys.iter().step_by(2).all(|key| !robin.contains_key(key)));
+    println!(
+        "罗宾汉哈希的探测长度方差 {:.2}，线性探测 {:.2}",
+        robin.probe_length_variance(),
+        variance
+    );
 }
 
-#[allow(dead_code)]
-impl HashMAPOpenAdressing {
-    /* 构造方法 */
-    fn new() -> Self {
-        Self { size: 0, 
-               capacity: 4,
-               load_thres: 2.0/3.0, 
-               extend_ratio: 2, 
-               buckets: vec![None;4], 
-               tombstone: Some(Pair { key: -1, value: "-1".to_string() }) }
-    }
-
-    /* 哈希函数 */
-    fn hash_func(&self, key: i32) -> usize{
-        (key % self.capacity as i32) as usize
-    }
-
-    /* 负载因子 */
-    fn load_factor(&self) -> f64{
-        self.size as f64 / self.capacity as f64
-    }
-
-    /* 搜索key对应的桶索引 */
-    fn find_bucket(&mut self, key: i32) -> usize {
-        let mut index = self.hash_func(key);
-        let mut first_tombstone = -1;
-        // 线性检测，当遇到空桶时跳出
-        while self.buckets[index].is_some() {
-            // 若遇到key，返回对应的桶索引
-            if self.buckets[index].as_ref().unwrap().key == key{
-                // 若之前遇到了删除标记，则将键值对移动至该索引
-                if first_tombstone != -1{
-                    self.buckets[first_tombstone as usize] = self.buckets[index].take();
-                    self.buckets[index] = self.tombstone.clone();
-                    return first_tombstone as usize;    // 返回移动的桶索引
+fn check_cuckoo() {
+    model_check!(CuckooMap::new());
+    model_check!(CuckooMap::with_seed(1).with_stash(4));
+    model_check!(CuckooMap::with_seed(2).with_hashes(3));
+    model_check!(CuckooMap::with_seed(3).with_ways(4).with_stash(2));
+
+    // 4路分桶在扩容之前能达到90%左右的负载因子，查询检查的位置数有固定上界
+    let keys = random_keys(5, 30_000, i64::MAX);
+    let mut map = CuckooMap::with_seed(4).with_ways(4);
+    let mut max_load: f64 = 0.0;
+    for &key in &keys {
+        map.insert(key, key);
+        max_load = max_load.max(map.load_factor());
+    }
+    assert!(max_load > 0.85);
+    assert_eq!(map.lookup_bound(), 8);
+    assert!(keys.iter().all(|key| map.get(key) == Some(key)));
+
+    // 阈值设得过高时驱逐链必然失败：换种子重新哈希，连续失败后扩容，键值对一个都不能丢
+    let mut map = CuckooMap::with_seed(6).with_load_thres(0.99);
+    for &key in &keys[..5_000] {
+        map.insert(key, ());
+    }
+    assert!(map.load_factor() < 0.6);
+    assert!(keys[..5_000].iter().all(|key| map.contains_key(key)));
+
+    // 负载因子接近50%时驱逐链经常失败，暂存区能吸收其中的大部分，减少重新哈希的次数
+    let rehashes = |stash: usize| {
+        (0..20u64)
+            .map(|seed| {
+                let mut map = CuckooMap::with_seed(seed)
+                    .with_stash(stash)
+                    .with_load_thres(0.49);
+                for &key in &keys[..2_000] {
+                    map.insert(key, ());
                 }
-                return index;   // 返回桶索引
-            }
-            // 记录遇到的首个删除标记
-            if first_tombstone == -1 && self.buckets[index] == self.tombstone{
-                first_tombstone = index as i32;
-            }
-            // 计算桶索引，越过尾部则返回头部
-            index = (index+1) % self.capacity;
-        }
-        // 若key不存在，则返回添加点的索引
-        if first_tombstone == -1{
-            index
-        }
-        else {
-            first_tombstone as usize
-        }
+                map.rehash_count()
+            })
+            .sum::<usize>()
+    };
+    let (without, with) = (rehashes(0), rehashes(4));
+    assert!(with < without);
+
+    // 删除后腾出的槽位会让暂存区中的键值对回到表中
+    let mut map = CuckooMap::with_seed(8).with_stash(4).with_load_thres(0.6);
+    for &key in &keys[..2_000] {
+        map.insert(key, ());
     }
+    for key in &keys[..1_000] {
+        map.remove(key);
+    }
+    assert_eq!(map.stash_len(), 0);
+    assert!(keys[1_000..2_000].iter().all(|key| map.contains_key(key)));
+    println!(
+        "布谷鸟哈希：重新哈希 {} 次，使用暂存区后 {} 次",
+        without, with
+    );
+}
+
+/* 直接把整数key当作哈希值，用来人为制造冲突 */
+#[derive(Default)]
+struct IdentityHasher(u64);
 
-    /* 查询操作 */
-    fn get(&mut self, key: i32) -> Option<&str> {
-        // 搜索key对应的桶索引
-        let index = self.find_bucket(key);
-        // 若找到键值对，则返回对应的value
-        if self.buckets[index].is_some() && self.buckets[index] != self.tombstone {
-            return self.buckets[index].as_ref().map(|pair| &pair.value as &str);
+impl Hasher for IdentityHasher {
+    // 其他类型的key：逐字节移入哈希值
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0 << 8 | byte as u64;
         }
-        // 若键值对不存在，则返回None
-        None
     }
 
-    /* 添加操作 */
-    fn put(&mut self, key: i32, value: String) {
-        // 当负载因子超过阈值时，执行扩容
-        if self.load_factor() > self.load_thres{
-            self.extend();
+    fn write_i64(&mut self, n: i64) {
+        self.0 = n as u64;
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn check_swiss() {
+    model_check!(SwissMap::new());
+    model_check!(SwissMap::with_hasher(Fixed::default()));
+
+    // h2(最高7位)全部相同、h1(低位)也只有少数几种取值：控制字节全部匹配，大量的组被填满，探测必须跨组进行
+    let mut map: SwissMap<i64, i64, BuildHasherDefault<IdentityHasher>> =
+        SwissMap::with_hasher(BuildHasherDefault::default());
+    let keys: Vec<i64> = (0..2000)
+        .map(|i| 0x2a << 57 | (i / 4) << 20 | (i % 4))
+        .collect();
+    for &key in &keys {
+        assert_eq!(map.insert(key, key), None);
+    }
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.get(key), Some(key));
+        if i % 3 == 0 {
+            assert_eq!(map.remove(key), Some(*key));
         }
-        // 搜索key对应的桶索引
-        let index = self.find_bucket(key);
-        // 若找到键值对，则覆盖value并返回
-        if self.buckets[index].is_some() && self.buckets[index] != self.tombstone {
-            self.buckets[index].as_mut().unwrap().value = value;
-            return;
+    }
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.contains_key(key), i % 3 != 0);
+        assert!(!map.contains_key(&(key + (1 << 10))));
+    }
+
+    // 反复插入删除，删除标记不会无限增长
+    let mut map = SwissMap::with_hasher(Fixed::default());
+    for round in 0..100i64 {
+        for key in 0..500 {
+            map.insert(key + round * 500, ());
         }
-        // 若键值对不存在，则添加该键值对
-        self.buckets[index] = Some(Pair { key: key, value: value });
-        self.size += 1;
-    }
-
-    /* 删除操作 */
-    fn remove(&mut self, key: i32) {
-        // 搜索key对应的桶索引
-        let index = self.find_bucket(key);
-        // 若找到该键值对，则用删除标记覆盖它
-        if self.buckets[index].is_some() && self.buckets[index] != self.tombstone{
-            self.buckets[index] = self.tombstone.clone();
-            self.size -= 1;
+        for key in 0..500 {
+            map.remove(&(key + round * 500));
         }
+        assert!(map.is_empty());
+        assert!(map.tombstones() as f64 <= map.capacity() as f64 * 7.0 / 8.0);
     }
+    assert!(map.capacity() <= 2048);
+    println!("SwissMap与HashMap的行为一致");
+}
 
-    /* 扩容哈希表 */
-    fn extend(&mut self){
-        // 暂存哈希表
-        let buckets_map = self.buckets.clone();
-        // 初始化扩容后的哈希表
-        self.capacity *= self.extend_ratio;
-        self.buckets = vec![None;self.capacity];
-        self.size = 0;
-
-        // 将键值对从原哈希表搬运至新哈希表
-        for pair in buckets_map{
-            if pair.is_none() || pair == self.tombstone{
-                continue;
+/* 所有key都是2^32的倍数，在IdentityHasher下哈希值的低32位全为0，无论容量是多少都落入0号桶 */
+fn check_treeify() {
+    type Colliding = BuildHasherDefault<IdentityHasher>;
+    for incremental in [false, true] {
+        let map = HashMapChaining::with_hasher(Colliding::default());
+        let mut map = if incremental { map.incremental(2) } else { map };
+        let mut model: HashMap<i64, usize> = HashMap::new();
+        for (i, key) in random_keys(11, 20_000, 600).into_iter().enumerate() {
+            let key = key << 32;
+            match i % 3 {
+                0 | 1 => assert_eq!(map.insert(key, i), model.insert(key, i)),
+                _ => assert_eq!(map.remove(&key), model.remove(&key)),
             }
-            let pair = pair.unwrap();
-
-            self.put(pair.key, pair.value);
+            assert_eq!(map.get(&key), model.get(&key));
+            assert_eq!(map.size(), model.len());
         }
+        assert!(model.iter().all(|(k, v)| map.get(k) == Some(v)));
+        assert!((-400..400).all(|k| map.contains_key(&(k << 32)) == model.contains_key(&(k << 32))));
     }
 
-    /* 打印哈希表 */
-    fn print(&self){
-        for pair in &self.buckets{
-            if pair.is_none(){
-                println!("NULL");
-            }
-            else if pair == &self.tombstone {
-                println!("TOMBSTONE");
-            }
-            else {
-                let pair = pair.as_ref().unwrap();
-            println!("{} -> {}", pair.key, pair.value);           
-            }
+    // 超过阈值后0号桶转换为树，键值对全部在这一个桶中
+    let mut map = HashMapChaining::with_hasher(Colliding::default());
+    for i in 0..5000i64 {
+        map.insert(i << 32, i);
+    }
+    assert_eq!(map.tree_buckets(), 1);
+    assert_eq!(map.max_chain_len(), 5000);
+    assert!((0..5000i64).all(|i| map.get(&(i << 32)) == Some(&i)));
+    assert!((5000..6000i64).all(|i| !map.contains_key(&(i << 32))));
+
+    // 删除到只剩6个时转换回列表；阈值之间留有间隔，在7和8之间来回增删不会反复转换
+    for i in 6..5000i64 {
+        assert_eq!(map.remove(&(i << 32)), Some(i));
+    }
+    assert_eq!(map.tree_buckets(), 0);
+    for i in 6..9i64 {
+        map.insert(i << 32, i);
+    }
+    assert_eq!(map.tree_buckets(), 1);
+    map.remove(&(8 << 32));
+    assert_eq!(map.tree_buckets(), 1);
+    assert!((0..8i64).all(|i| map.get(&(i << 32)) == Some(&i)));
+    println!("冲突的key集中在一个桶中时，链表会转换为树");
+}
+
+/* 预留空间后插入不再扩容；删除大部分key后自动缩容；在刚扩容完的位置交替插入删除，容量保持不变 */
+macro_rules! resize_check {
+    ($map:expr) => {{
+        let mut map = $map;
+        let keys = random_keys(21, 10_000, i64::MAX);
+        map.reserve(keys.len());
+        let reserved = map.capacity();
+        for &key in &keys {
+            map.insert(key, key);
         }
+        assert_eq!(map.capacity(), reserved);
+
+        for key in &keys[1_000..] {
+            assert_eq!(map.remove(key), Some(*key));
+        }
+        let shrunk = map.capacity();
+        assert!(shrunk < reserved);
+        map.shrink_to_fit();
+        assert!(map.capacity() <= shrunk);
+        assert!(keys[..1_000].iter().all(|key| map.get(key) == Some(key)));
+
+        let mut next = keys[1_000..].iter();
+        let capacity = map.capacity();
+        let mut last = 0;
+        while map.capacity() == capacity {
+            last = *next.next().expect("key足够多，总会扩容");
+            map.insert(last, last);
+        }
+        let capacity = map.capacity();
+        for _ in 0..1_000 {
+            map.remove(&last);
+            map.insert(last, last);
+            assert_eq!(map.capacity(), capacity);
+        }
+        map
+    }};
+}
+
+fn check_resize_policy() {
+    let is_prime = |n: usize| {
+        n >= 2
+            && (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    };
+    let prime = ResizePolicy::new(0.75).with_capacity_kind(CapacityKind::Prime);
+    let eager = ResizePolicy::new(0.5).with_shrink_thres(0.2);
+    let wide = ResizePolicy::new(0.8)
+        .with_shrink_thres(0.1)
+        .with_growth_factor(4)
+        .with_min_capacity(64);
+
+    // 阈值与容量的计算
+    assert_eq!(prime.round_capacity(20), 23);
+    assert_eq!(prime.capacity_for(30), 41);
+    assert_eq!(eager.grown(8), 16);
+    assert_eq!(eager.shrunk(3, 64), Some(16));
+    assert_eq!(eager.shrunk(13, 64), None);
+    assert_eq!(wide.round_capacity(1), 64);
+
+    resize_check!(HashMapChaining::with_hasher(Fixed::default()));
+    resize_check!(HashMapChaining::new().incremental(2).with_policy(eager));
+    let map = resize_check!(HashMapChaining::new().with_policy(prime));
+    assert!(is_prime(map.capacity()));
+    resize_check!(OpenAddressingMap::new());
+    resize_check!(OpenAddressingMap::with_strategy(QuadraticProbing).with_policy(wide));
+    resize_check!(RobinHoodMap::new());
+    let map = resize_check!(RobinHoodMap::new().with_policy(prime));
+    assert!(is_prime(map.capacity()));
+    resize_check!(CuckooMap::with_seed(9).with_ways(4));
+    let map = resize_check!(CuckooMap::with_seed(10).with_policy(prime.with_grow_thres(0.4)));
+    assert!(is_prime(map.capacity()));
+    resize_check!(SwissMap::new());
+    resize_check!(SwissMap::new().with_policy(wide));
+
+    // 任意策略下，哈希表的行为都与HashMap一致
+    model_check!(HashMapChaining::new().incremental(1).with_policy(prime));
+    model_check!(OpenAddressingMap::with_hasher(Fixed::default()).with_policy(eager));
+    model_check!(RobinHoodMap::new().with_policy(eager));
+    model_check!(CuckooMap::with_seed(11).with_stash(2).with_policy(eager));
+    model_check!(SwissMap::new().with_policy(wide));
+
+    // 只删不插时容量一路缩小到最小容量
+    let mut map = OpenAddressingMap::with_hasher(Fixed::default()).with_policy(eager);
+    for key in 0..100_000 {
+        map.insert(key, ());
     }
+    let peak = map.capacity();
+    for key in 0..100_000 {
+        map.remove(&key);
+    }
+    assert_eq!(map.capacity(), eager.min_capacity);
+    println!(
+        "扩容与缩容策略：峰值容量 {}，全部删除后缩容至 {}",
+        peak,
+        map.capacity()
+    );
 }
 
-/* 平方探测：
-    平方探测与线性检测类似，都是开放寻址的常见策略之一。当冲突发生时，平方探测不是简单地跳过一个固定的步数，而是跳过“探测次数的平方”的步数。
-    平方探测主要具有以下优势：
-        1.平方探测会跳过探测次数平方的距离，试图缓解线性检测的聚集效应。
-        2.平方探测会跳过更大的距离来寻找空位置，有助于使数据分布更均匀。
-    然而，平方探测并不是完美的。
-        1.仍然会存在聚集现象，即某些位置比其他位置更容易被占用。
-        2.由于平方的增长，平方探测可能不会探测整个哈希表，这意味着即使哈希表中有空桶，平方探测也可能无法访问它。 */
-
-/* 多次哈希：
-    顾名思义，多次哈希方法就是通过多次哈希函数进行探测。
-        1.插入元素：若哈希函数1出现冲突，则尝试哈希函数2，以此类推，直到找到空位后插入元素。
-        2.查找元素：在相同的哈希函数顺序下进行查找，直到找到目标元素时返回；若遇到空位或已尝试所有哈希函数，说明哈希表中不存在这个元素，返回None。
-    与线性探测相比，多次哈希方法不易产生聚集，但多个哈希函数会带来额外的计算量。 */
+/* 插入10000个key后取统计快照：每次扩容搬运的数量构成等比数列，总量不超过键值对数量的2倍；再删除一半，返回删除后的快照 */
+macro_rules! stats_check {
+    ($map:expr) => {{
+        let mut map = $map;
+        let keys = random_keys(31, 10_000, i64::MAX);
+        for &key in &keys {
+            map.insert(key, key);
+        }
+        let stats = map.stats();
+        // 渐进式扩容期间，链式地址的桶数还包括尚未搬运的旧桶
+        assert_eq!(stats.size, map.size());
+        assert!(stats.capacity >= map.capacity());
+        assert!(stats.resizes >= 1);
+        assert!(stats.rehash_work <= 2 * stats.size);
+        for key in &keys[..5_000] {
+            map.remove(key);
+        }
+        let stats = map.stats();
+        assert_eq!(stats.size, 5_000);
+        assert_eq!(stats.max_probe + 1, stats.histogram.len());
+        assert!(stats.histogram[stats.max_probe] > 0);
+        (map, stats)
+    }};
+}
 
-fn main() {
-    let mut hash_map: HashMap<i32 , String> = HashMap::new();
-    hash_map.insert(1, "wudi".to_string());
-    println!("{:?}", hash_map);
-}
\ No newline at end of file
+fn check_stats() {
+    // 链式地址的直方图按桶统计：各格之和是桶数，加权和是键值对数量
+    let check_buckets = |stats: &MapStats| {
+        assert_eq!(stats.histogram.iter().sum::<usize>(), stats.capacity);
+        let pairs: usize = stats
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(len, n)| len * n)
+            .sum();
+        assert_eq!(pairs, stats.size);
+    };
+    let (_, chaining) = stats_check!(HashMapChaining::with_hasher(Fixed::default()));
+    check_buckets(&chaining);
+
+    // 渐进式扩容的中途：尚未搬运的旧桶既计入直方图，也计入桶数
+    let (mut map, _) = stats_check!(HashMapChaining::with_hasher(Fixed::default()).incremental(1));
+    let mut key = 0;
+    while !map.is_rehashing() {
+        map.insert(key, key);
+        key += 1;
+    }
+    map.insert(key, key);
+    let stats = map.stats();
+    assert!(map.is_rehashing());
+    assert!(stats.capacity > map.capacity());
+    check_buckets(&stats);
+
+    // 其余哈希表按键值对统计探测长度：各格之和是键值对数量，探测长度至少为1
+    let (_, open) = stats_check!(OpenAddressingMap::with_hasher(Fixed::default()));
+    let (robin_map, robin) = stats_check!(RobinHoodMap::with_hasher(Fixed::default()));
+    let (cuckoo_map, cuckoo) = stats_check!(CuckooMap::with_seed(12).with_stash(2));
+    let (_, swiss) = stats_check!(SwissMap::with_hasher(Fixed::default()));
+    for stats in [&open, &robin, &cuckoo, &swiss] {
+        assert_eq!(stats.histogram.iter().sum::<usize>(), stats.size);
+        assert_eq!(stats.histogram[0], 0);
+    }
+    assert_eq!(robin.max_probe, robin_map.max_probe_length());
+    assert!((robin.mean_probe() - robin_map.mean_probe_length()).abs() < 1e-9);
+    assert!(cuckoo.max_probe <= cuckoo_map.lookup_bound());
+    assert!(open.tombstones > 0);
+
+    // 纯插入时每次扩容容量翻倍，扩容次数由容量决定
+    let mut map = RobinHoodMap::with_hasher(Fixed::default());
+    for key in 0..1000 {
+        map.insert(key, ());
+    }
+    let stats = map.stats();
+    assert_eq!(1 << stats.resizes, stats.capacity / 4);
+
+    // CSV与JSON：每份快照一行，名字中的特殊字符需要转义
+    let rows = [
+        ("chaining", &chaining),
+        ("open_addressing", &open),
+        ("robin_hood", &robin),
+        ("cuckoo", &cuckoo),
+        ("swiss", &swiss),
+    ];
+    let csv = stats_csv(&rows);
+    assert_eq!(csv.lines().count(), rows.len() + 1);
+    assert!(csv.lines().all(|line| line.split(',').count() == 10));
+    let json = stats_json(&rows);
+    assert!(json.starts_with("[\n") && json.ends_with("]\n"));
+    assert_eq!(json.matches("\"name\":").count(), rows.len());
+    assert!(stats_csv(&[("a,\"b\"", &swiss)]).contains("\n\"a,\"\"b\"\"\","));
+    assert!(swiss
+        .to_json("a\"b\\\n")
+        .starts_with("{\"name\":\"a\\\"b\\\\\\n\","));
+    print!("{}", csv);
+    println!("统计快照可以导出为CSV与JSON");
+}
@@ -0,0 +1,418 @@
+/* 2.开放寻址
+        开放寻址不引入额外的数据结构，而是通过“多次探测”来处理哈希冲突，探测方式主要包含线性探测、平方探测和多次哈希等。
+        1.线性探测
+        线性探测采用固定步长的线性搜索来进行探测，其操作方法和普通哈希表有所不同。
+            1.插入元素：通过哈希函数计算bucket索引，若发现bucket内已有元素，则从冲突位置向后线性遍历(步长通常为1),知道找到空bucket，将元素插入其中。
+            2.查找元素：若发现哈希冲突，则使用相同步长向后进行线性遍历，知道找到对应的元素，返回value即可；如果遇到空bucket，说明元素不在哈希表中，返回None。
+        线性检测容易产生“聚集现象”。
+        我们不能在开放寻址哈希表中直接删除元素。这是因为删除元素会在数组内产生一个空桶None，而当查询元素时，线性探测到该空bucket就会返回，因此在该空bucket之下的元素都无法再被访问到，程序可能会误判这些元素不存在。
+        为了解决该问题，我们可以采用懒删除(lazy deletion)机制：它不直接从哈希表中移除元素，而是利用一个常量TOMBSTONE来标记这个桶。在该机制下，
+        None和TOMBSTONE都代表空桶，都可以放置键值对。但是不同的是，线性检测到TOMBSTONE时应该继续遍历，因为在其下还可能存在键值对。
+        然而，懒删除可能会加速哈希表的性能退化。这是因为在每一次删除操作时都会产生一个删除标记，随着TOMBSTONE的增加，搜索时间也会增加，因为线性检测可能需要跳过多个TOMBSTONE才能找到目标元素。
+        为此，可以考虑在线性探测中记录首个TOMBSTONE的索引，并将搜索到的目标元素与该TOMBSTONE交换位置。这样做的好处是每当查询或者添加元素的时候，元素会被移动至距离理想位置(探测起始点)更近的桶，从而优化查询效率。
+        删除标记同样占据桶，探测只有遇到真正的空桶才会停止。因此判断是否需要重建时，要把“键值对 + 删除标记”一起计入负载：
+        若其中键值对本身已经很多，则扩容；否则说明主要是删除标记，按原容量原地重建哈希表，把删除标记全部清除。
+        探测方式由ProbeStrategy决定，默认是线性探测，也可以换成平方探测或双重哈希，见probe.rs。哈希表的容量始终是2的幂。
+        扩容与缩容由ResizePolicy决定(见resize.rs)，由于探测策略依赖2的幂容量，这里不接受质数容量。
+         */
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::probe::{LinearProbing, ProbeStrategy};
+use crate::resize::{CapacityKind, ResizePolicy};
+use crate::stats::MapStats;
+
+/* 桶的状态 */
+enum Bucket<K, V> {
+    Empty,          // 从未存放过键值对，探测到这里即可停止
+    Occupied(K, V), // 存放着一个键值对
+    Tombstone,      // 删除标记：键值对已被删除，探测需要越过它继续
+}
+
+/* 开放寻址哈希表 */
+pub struct OpenAddressingMap<K, V, S = RandomState, P = LinearProbing> {
+    size: usize,                // 键值对数量
+    tombstones: usize,          // 删除标记数量
+    capacity: usize,            // 哈希表容量，始终是2的幂
+    policy: ResizePolicy,       // 扩容与缩容策略，扩容阈值按键值对与删除标记一起计算
+    buckets: Vec<Bucket<K, V>>, // 桶数组
+    resizes: usize,             // 扩容、缩容与原地重建的次数
+    rehash_work: usize,         // 累计搬运的键值对数量
+    hasher: S,
+    strategy: P,
+}
+
+/* 探测的结果 */
+enum Probe {
+    // 找到key所在的桶，以及探测路径上位于它之前的首个删除标记
+    Found {
+        index: usize,
+        tombstone: Option<usize>,
+    },
+    // key不存在，返回可以插入的桶(首个删除标记或空桶)
+    Vacant(usize),
+}
+
+impl<K: Hash + Eq, V> OpenAddressingMap<K, V, RandomState> {
+    /* 构造方法 */
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> OpenAddressingMap<K, V, S> {
+    // 指定哈希算法的构造方法
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_hasher_and_strategy(hasher, LinearProbing)
+    }
+}
+
+impl<K: Hash + Eq, V, P: ProbeStrategy> OpenAddressingMap<K, V, RandomState, P> {
+    // 指定探测策略的构造方法
+    pub fn with_strategy(strategy: P) -> Self {
+        Self::with_hasher_and_strategy(RandomState::new(), strategy)
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher, P: ProbeStrategy> OpenAddressingMap<K, V, S, P> {
+    // 指定哈希算法与探测策略的构造方法
+    pub fn with_hasher_and_strategy(hasher: S, strategy: P) -> Self {
+        Self {
+            size: 0,
+            tombstones: 0,
+            capacity: 4,
+            policy: ResizePolicy::new(2.0 / 3.0),
+            buckets: (0..4).map(|_| Bucket::Empty).collect(),
+            resizes: 0,
+            rehash_work: 0,
+            hasher,
+            strategy,
+        }
+    }
+
+    /* 设置负载因子阈值，阈值越高越省空间，但探测路径越长；其余参数取默认值 */
+    pub fn with_load_thres(self, load_thres: f64) -> Self {
+        self.with_policy(ResizePolicy::new(load_thres))
+    }
+
+    /* 设置扩容与缩容策略，只能在插入键值对之前调用 */
+    pub fn with_policy(mut self, policy: ResizePolicy) -> Self {
+        assert!(self.is_empty(), "只能为空哈希表设置扩容策略");
+        assert!(policy.grow_thres < 1.0, "扩容阈值必须小于1");
+        assert!(
+            policy.capacity_kind == CapacityKind::PowerOfTwo,
+            "探测策略要求容量是2的幂"
+        );
+        self.policy = policy;
+        self.capacity = policy.round_capacity(policy.min_capacity);
+        self.buckets = (0..self.capacity).map(|_| Bucket::Empty).collect();
+        self
+    }
+
+    /* 负载因子 */
+    pub fn load_factor(&self) -> f64 {
+        self.size as f64 / self.capacity as f64
+    }
+
+    // 键值对数量
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 判断哈希表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 桶的数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 扩容与缩容策略
+    pub fn policy(&self) -> ResizePolicy {
+        self.policy
+    }
+
+    // 删除标记的数量
+    pub fn tombstones(&self) -> usize {
+        self.tombstones
+    }
+
+    /* 按探测策略搜索key，同时返回访问过的桶数量 */
+    fn probe<Q>(&self, key: &Q) -> (Probe, usize)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        let mut index = 0;
+        let mut first_tombstone = None;
+        // 当遇到空桶时跳出；最多检测capacity个桶，防止表中没有空桶时陷入死循环
+        for i in 0..self.capacity {
+            index = self.strategy.index(hash, i, self.capacity);
+            match &self.buckets[index] {
+                // 若遇到key，返回对应的桶索引
+                Bucket::Occupied(k, _) if k.borrow() == key => {
+                    let tombstone = first_tombstone;
+                    return (Probe::Found { index, tombstone }, i + 1);
+                }
+                Bucket::Occupied(..) => {}
+                // 记录遇到的首个删除标记
+                Bucket::Tombstone => {
+                    first_tombstone.get_or_insert(index);
+                }
+                Bucket::Empty => return (Probe::Vacant(first_tombstone.unwrap_or(index)), i + 1),
+            }
+        }
+        // 若key不存在，则返回添加点的索引
+        (
+            Probe::Vacant(first_tombstone.unwrap_or(index)),
+            self.capacity,
+        )
+    }
+
+    /* 搜索key对应的桶索引 */
+    fn find_bucket<Q>(&self, key: &Q) -> Probe
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.probe(key).0
+    }
+
+    /* 搜索key，并把找到的键值对移动到探测路径上的首个删除标记处，使它更靠近理想位置 */
+    fn find_bucket_mut<Q>(&mut self, key: &Q) -> Probe
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.find_bucket(key) {
+            // 若之前遇到了删除标记，则将键值对移动至该索引，原位置变为删除标记，数量不变
+            Probe::Found {
+                index,
+                tombstone: Some(t),
+            } => {
+                self.buckets.swap(t, index);
+                Probe::Found {
+                    index: t,
+                    tombstone: None,
+                }
+            }
+            probe => probe,
+        }
+    }
+
+    /* 查询key需要访问的桶数量，key不存在时为确认其不存在所需的数量 */
+    pub fn probe_count<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.probe(key).1
+    }
+
+    /* 查询操作 */
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // 若找到键值对，则返回对应的value
+        match self.find_bucket(key) {
+            Probe::Found { index, .. } => match &self.buckets[index] {
+                Bucket::Occupied(_, value) => Some(value),
+                _ => None,
+            },
+            // 若键值对不存在，则返回None
+            Probe::Vacant(_) => None,
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.find_bucket_mut(key) {
+            Probe::Found { index, .. } => match &mut self.buckets[index] {
+                Bucket::Occupied(_, value) => Some(value),
+                _ => None,
+            },
+            Probe::Vacant(_) => None,
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        matches!(self.find_bucket(key), Probe::Found { .. })
+    }
+
+    /* 添加操作：key已存在时覆盖value，并返回旧的value */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        // 键值对与删除标记一起超过阈值时，扩容或原地重建
+        if self
+            .policy
+            .should_grow(self.size + self.tombstones, self.capacity)
+        {
+            self.rebuild();
+        }
+        // 搜索key对应的桶索引
+        match self.find_bucket_mut(&key) {
+            // 若找到键值对，则覆盖value并返回
+            Probe::Found { index, .. } => match &mut self.buckets[index] {
+                Bucket::Occupied(_, old) => Some(std::mem::replace(old, value)),
+                _ => unreachable!("找到的桶中有键值对"),
+            },
+            // 若键值对不存在，则添加该键值对
+            Probe::Vacant(index) => {
+                let old = std::mem::replace(&mut self.buckets[index], Bucket::Occupied(key, value));
+                if let Bucket::Tombstone = old {
+                    self.tombstones -= 1;
+                }
+                self.size += 1;
+                None
+            }
+        }
+    }
+
+    /* 删除操作：返回被删除的value */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // 若找到该键值对，则用删除标记覆盖它
+        let Probe::Found { index, .. } = self.find_bucket(key) else {
+            return None;
+        };
+        let Bucket::Occupied(_, value) =
+            std::mem::replace(&mut self.buckets[index], Bucket::Tombstone)
+        else {
+            unreachable!("找到的桶中有键值对");
+        };
+        self.size -= 1;
+        self.tombstones += 1;
+        // 负载因子低于缩容阈值时缩容；只删不插时也不能让删除标记堆满整张表，超过一半时原地重建
+        if let Some(capacity) = self.policy.shrunk(self.size, self.capacity) {
+            self.rehash(capacity);
+        } else if self.tombstones * 2 > self.capacity {
+            self.rehash(self.capacity);
+        }
+        Some(value)
+    }
+
+    /* 预留空间：之后再插入additional个键值对都不会触发扩容 */
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.policy.capacity_for(self.size + additional);
+        if capacity > self.capacity {
+            self.rehash(capacity);
+        }
+    }
+
+    /* 把容量缩小到恰好能容纳现有键值对，删除标记随之清空 */
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.policy.capacity_for(self.size);
+        if capacity < self.capacity {
+            self.rehash(capacity);
+        }
+    }
+
+    /* 负载过高时重建：键值对本身超过阈值的一半则扩容，否则主要是删除标记，按原容量重建 */
+    fn rebuild(&mut self) {
+        if self.load_factor() > self.policy.grow_thres / 2.0 {
+            self.extend();
+        } else {
+            self.rehash(self.capacity);
+        }
+    }
+
+    /* 扩容哈希表 */
+    fn extend(&mut self) {
+        self.rehash(self.policy.grown(self.capacity));
+    }
+
+    /* 按给定容量重建哈希表，删除标记随之清空 */
+    fn rehash(&mut self, capacity: usize) {
+        // 暂存哈希表
+        let buckets_tmp = std::mem::take(&mut self.buckets);
+        // 初始化新的哈希表
+        self.capacity = capacity;
+        self.buckets = (0..self.capacity).map(|_| Bucket::Empty).collect();
+        self.size = 0;
+        self.tombstones = 0;
+        self.resizes += 1;
+
+        // 将键值对从原哈希表搬运至新哈希表
+        for bucket in buckets_tmp {
+            if let Bucket::Occupied(key, value) = bucket {
+                self.insert(key, value);
+                self.rehash_work += 1;
+            }
+        }
+    }
+
+    /* 统计快照：直方图统计每个键值对的探测长度 */
+    pub fn stats(&self) -> MapStats {
+        let histogram = MapStats::histogram_of(self.keys().map(|key| self.probe_count(key)));
+        MapStats {
+            size: self.size,
+            capacity: self.capacity,
+            load_factor: self.load_factor(),
+            max_probe: histogram.len().saturating_sub(1),
+            histogram,
+            tombstones: self.tombstones,
+            resizes: self.resizes,
+            rehash_work: self.rehash_work,
+        }
+    }
+}
+
+impl<K, V, S, P> OpenAddressingMap<K, V, S, P> {
+    /* 遍历所有键值对 */
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.buckets.iter().filter_map(|bucket| match bucket {
+            Bucket::Occupied(key, value) => Some((key, value)),
+            _ => None,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S, P> OpenAddressingMap<K, V, S, P> {
+    /* 打印哈希表 */
+    pub fn print(&self) {
+        for bucket in &self.buckets {
+            match bucket {
+                Bucket::Occupied(key, value) => println!("{:?} -> {:?}", key, value),
+                Bucket::Tombstone => println!("TOMBSTONE"),
+                Bucket::Empty => println!("NULL"),
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for OpenAddressingMap<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S, P> fmt::Debug for OpenAddressingMap<K, V, S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,57 @@
+/* 平方探测：
+    平方探测与线性检测类似，都是开放寻址的常见策略之一。当冲突发生时，平方探测不是简单地跳过一个固定的步数，而是跳过“探测次数的平方”的步数。
+    平方探测主要具有以下优势：
+        1.平方探测会跳过探测次数平方的距离，试图缓解线性检测的聚集效应。
+        2.平方探测会跳过更大的距离来寻找空位置，有助于使数据分布更均匀。
+    然而，平方探测并不是完美的。
+        1.仍然会存在聚集现象，即某些位置比其他位置更容易被占用。
+        2.由于平方的增长，平方探测可能不会探测整个哈希表，这意味着即使哈希表中有空桶，平方探测也可能无法访问它。
+    这里的实现使用三角数 i(i+1)/2 代替 i²：当容量是2的幂时，前capacity次探测恰好不重不漏地访问每一个桶，解决了第2个问题。 */
+
+/* 多次哈希：
+    顾名思义，多次哈希方法就是通过多次哈希函数进行探测。
+        1.插入元素：若哈希函数1出现冲突，则尝试哈希函数2，以此类推，直到找到空位后插入元素。
+        2.查找元素：在相同的哈希函数顺序下进行查找，直到找到目标元素时返回；若遇到空位或已尝试所有哈希函数，说明哈希表中不存在这个元素，返回None。
+    与线性探测相比，多次哈希方法不易产生聚集，但多个哈希函数会带来额外的计算量。
+    常用的双重哈希(double hashing)是它的一种形式：第一个哈希值决定起点，第二个哈希值决定步长，第i次探测的位置为 h1 + i * h2。
+    不同的key即使起点相同，步长也大概率不同，因此探测路径会很快分开。容量是2的幂时，只要步长是奇数，就能访问到每一个桶。 */
+
+/* 探测策略：给出第i次探测(i从0开始)的桶索引
+    hash是key的完整64位哈希值，capacity必须是2的幂。
+    在容量为2的幂时，对2^64取余的回绕不影响对capacity取余的结果，因此可以放心使用wrapping运算。 */
+pub trait ProbeStrategy {
+    fn index(&self, hash: u64, i: usize, capacity: usize) -> usize;
+}
+
+/* 线性探测：h, h+1, h+2, ... */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearProbing;
+
+impl ProbeStrategy for LinearProbing {
+    fn index(&self, hash: u64, i: usize, capacity: usize) -> usize {
+        (hash.wrapping_add(i as u64) % capacity as u64) as usize
+    }
+}
+
+/* 平方探测(三角数)：h, h+1, h+3, h+6, ... */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuadraticProbing;
+
+impl ProbeStrategy for QuadraticProbing {
+    fn index(&self, hash: u64, i: usize, capacity: usize) -> usize {
+        let i = i as u64;
+        (hash.wrapping_add(i * (i + 1) / 2) % capacity as u64) as usize
+    }
+}
+
+/* 双重哈希：h1, h1+h2, h1+2*h2, ...
+    h1取哈希值的低位，h2取高32位并强制为奇数，两者近似独立，无需再计算一次哈希。 */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoubleHashing;
+
+impl ProbeStrategy for DoubleHashing {
+    fn index(&self, hash: u64, i: usize, capacity: usize) -> usize {
+        let step = (hash >> 32) | 1;
+        (hash.wrapping_add((i as u64).wrapping_mul(step)) % capacity as u64) as usize
+    }
+}
@@ -0,0 +1,115 @@
+/* 扩容与缩容策略
+    哈希表在负载因子超过扩容阈值时扩容；大量删除之后，负载因子低于缩容阈值时缩容，把多余的内存还回去。
+    两个阈值之间要留出足够的间隔(滞后，hysteresis)：扩容后的负载因子必须高于缩容阈值，缩容后的负载因子必须低于扩容阈值，
+    否则在阈值附近交替插入和删除时，哈希表会反复扩容、缩容。这里要求 缩容阈值 * 扩容倍数 < 扩容阈值，
+    并且缩容时把负载因子恢复到 扩容阈值 / 扩容倍数 左右，与刚扩容完时相同。
+    容量可以取2的幂或质数：
+        1.2的幂：取余可以用位运算代替，平方探测(三角数)与SwissTable的分组探测都依赖它，但哈希值的低位质量差时冲突多。
+        2.质数：即使哈希函数较弱(例如 key % capacity)，key也能分布得比较均匀。 */
+
+/* 容量的取值方式 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapacityKind {
+    PowerOfTwo,
+    Prime,
+}
+
+/* 扩容与缩容策略 */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResizePolicy {
+    pub grow_thres: f64,             // 负载因子超过该值时扩容
+    pub shrink_thres: f64,           // 负载因子低于该值时缩容，为0表示从不缩容
+    pub growth_factor: usize,        // 扩容倍数
+    pub min_capacity: usize,         // 最小容量，缩容不会低于它
+    pub capacity_kind: CapacityKind, // 容量取2的幂还是质数
+}
+
+impl ResizePolicy {
+    /* 构造方法：缩容阈值默认为扩容阈值的1/4，扩容倍数为2，最小容量为4，容量取2的幂 */
+    pub fn new(grow_thres: f64) -> Self {
+        Self {
+            grow_thres,
+            shrink_thres: grow_thres / 4.0,
+            growth_factor: 2,
+            min_capacity: 4,
+            capacity_kind: CapacityKind::PowerOfTwo,
+        }
+        .validated()
+    }
+
+    pub fn with_grow_thres(mut self, grow_thres: f64) -> Self {
+        self.grow_thres = grow_thres;
+        self.validated()
+    }
+
+    pub fn with_shrink_thres(mut self, shrink_thres: f64) -> Self {
+        self.shrink_thres = shrink_thres;
+        self.validated()
+    }
+
+    pub fn with_growth_factor(mut self, growth_factor: usize) -> Self {
+        self.growth_factor = growth_factor;
+        self.validated()
+    }
+
+    pub fn with_min_capacity(mut self, min_capacity: usize) -> Self {
+        self.min_capacity = min_capacity;
+        self.validated()
+    }
+
+    pub fn with_capacity_kind(mut self, capacity_kind: CapacityKind) -> Self {
+        self.capacity_kind = capacity_kind;
+        self
+    }
+
+    // 检查参数是否合理
+    fn validated(self) -> Self {
+        assert!(self.grow_thres > 0.0, "扩容阈值必须大于0");
+        assert!(self.shrink_thres >= 0.0, "缩容阈值不能为负数");
+        assert!(self.growth_factor >= 2, "扩容倍数至少为2");
+        assert!(self.min_capacity >= 1, "最小容量至少为1");
+        assert!(
+            self.shrink_thres * (self.growth_factor as f64) < self.grow_thres,
+            "缩容阈值乘以扩容倍数必须小于扩容阈值，否则会反复扩容缩容"
+        );
+        self
+    }
+
+    /* 把容量调整为不小于n的合法值 */
+    pub fn round_capacity(&self, n: usize) -> usize {
+        let n = n.max(self.min_capacity);
+        match self.capacity_kind {
+            CapacityKind::PowerOfTwo => n.next_power_of_two(),
+            CapacityKind::Prime => (n..).find(|&c| is_prime(c)).expect("质数有无穷多个"),
+        }
+    }
+
+    /* 容纳len个键值对而不触发扩容的最小容量 */
+    pub fn capacity_for(&self, len: usize) -> usize {
+        self.round_capacity((len as f64 / self.grow_thres).ceil() as usize)
+    }
+
+    /* len个键值对放在capacity个桶中时是否需要扩容 */
+    pub fn should_grow(&self, len: usize, capacity: usize) -> bool {
+        len as f64 > capacity as f64 * self.grow_thres
+    }
+
+    /* 扩容后的容量 */
+    pub fn grown(&self, capacity: usize) -> usize {
+        self.round_capacity(capacity * self.growth_factor)
+    }
+
+    /* 缩容后的容量：负载因子恢复到扩容阈值 / 扩容倍数左右；不需要缩容时返回None */
+    pub fn shrunk(&self, len: usize, capacity: usize) -> Option<usize> {
+        if len as f64 >= capacity as f64 * self.shrink_thres {
+            return None;
+        }
+        let target = self.capacity_for(len * self.growth_factor);
+        (target < capacity).then_some(target)
+    }
+}
+
+/* 试除法判断质数 */
+fn is_prime(n: usize) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
@@ -0,0 +1,351 @@
+/* 罗宾汉哈希(Robin Hood hashing)
+        线性探测的问题在于“聚集现象”：一段连续被占用的桶会越来越长，落在其中的key需要走很远才能找到空桶，
+        于是少数key的探测距离特别长，而查询的最坏情况正是由它们决定的。
+        罗宾汉哈希仍然使用线性探测，但为每个桶记录其中键值对的探测距离(dist，即离理想位置有多远)，并在插入时“劫富济贫”：
+            1.插入元素：从理想位置开始向后探测，若遇到的键值对比手中的键值对离理想位置更近(更“富”)，就把桶让给手中的键值对，
+            转而拿着被挤出来的键值对继续向后探测，直到遇到空桶。这样所有键值对的探测距离会趋于平均，方差显著减小。
+            2.查找元素：探测过程中若遇到空桶，或遇到的键值对的dist比当前已走的距离还小，说明目标key不可能在更后面(否则插入时它就会抢占这个桶)，可以提前结束。
+            3.删除元素：不使用删除标记，而是采用“后移删除”(backward-shift deletion)：删除后把后面dist大于0的键值对依次前移一格，
+            直到遇到空桶或dist为0的键值对。这样表中始终没有删除标记，也就不存在删除标记拖慢查询的问题。
+        由于探测距离更加均匀，负载因子阈值可以设得比普通线性探测更高。扩容与缩容由ResizePolicy决定(见resize.rs)，
+        桶索引直接对容量取余，因此容量既可以是2的幂，也可以是质数。 */
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::resize::ResizePolicy;
+use crate::stats::MapStats;
+
+/* 桶中的键值对，以及它离理想位置的距离 */
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    dist: usize,
+}
+
+/* 罗宾汉哈希表 */
+pub struct RobinHoodMap<K, V, S = RandomState> {
+    size: usize,                      // 键值对数量
+    capacity: usize,                  // 哈希表容量
+    policy: ResizePolicy,             // 扩容与缩容策略
+    buckets: Vec<Option<Slot<K, V>>>, // 桶数组
+    resizes: usize,                   // 扩容与缩容的次数
+    rehash_work: usize,               // 累计搬运的键值对数量
+    hasher: S,
+}
+
+impl<K: Hash + Eq, V> RobinHoodMap<K, V, RandomState> {
+    /* 构造方法 */
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> RobinHoodMap<K, V, S> {
+    // 指定哈希算法的构造方法
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            size: 0,
+            capacity: 4,
+            policy: ResizePolicy::new(0.9),
+            buckets: (0..4).map(|_| None).collect(),
+            resizes: 0,
+            rehash_work: 0,
+            hasher,
+        }
+    }
+
+    /* 设置负载因子阈值，其余参数取默认值 */
+    pub fn with_load_thres(self, load_thres: f64) -> Self {
+        self.with_policy(ResizePolicy::new(load_thres))
+    }
+
+    /* 设置扩容与缩容策略，只能在插入键值对之前调用 */
+    pub fn with_policy(mut self, policy: ResizePolicy) -> Self {
+        assert!(self.is_empty(), "只能为空哈希表设置扩容策略");
+        assert!(policy.grow_thres < 1.0, "扩容阈值必须小于1");
+        self.policy = policy;
+        self.capacity = policy.round_capacity(policy.min_capacity);
+        self.buckets = (0..self.capacity).map(|_| None).collect();
+        self
+    }
+
+    /* 哈希函数：key的理想位置 */
+    fn hash_func<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        (self.hasher.hash_one(key) % self.capacity as u64) as usize
+    }
+
+    /* 负载因子 */
+    pub fn load_factor(&self) -> f64 {
+        self.size as f64 / self.capacity as f64
+    }
+
+    // 键值对数量
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 判断哈希表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 桶的数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 扩容与缩容策略
+    pub fn policy(&self) -> ResizePolicy {
+        self.policy
+    }
+
+    /* 搜索key所在的桶索引，同时返回访问过的桶数量 */
+    fn search<Q>(&self, key: &Q) -> (Option<usize>, usize)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut index = self.hash_func(key);
+        for dist in 0..self.capacity {
+            match &self.buckets[index] {
+                Some(slot) if slot.key.borrow() == key => return (Some(index), dist + 1),
+                // 遇到更“富”的键值对：若key存在，插入时早就抢占了这个桶，因此可以提前结束
+                Some(slot) if slot.dist < dist => return (None, dist + 1),
+                Some(_) => {}
+                None => return (None, dist + 1),
+            }
+            index = (index + 1) % self.capacity;
+        }
+        (None, self.capacity)
+    }
+
+    /* 查询操作 */
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.search(key).0?;
+        self.buckets[index].as_ref().map(|slot| &slot.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.search(key).0?;
+        self.buckets[index].as_mut().map(|slot| &mut slot.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.search(key).0.is_some()
+    }
+
+    /* 查询key需要访问的桶数量，key不存在时为确认其不存在所需的数量 */
+    pub fn probe_count<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.search(key).1
+    }
+
+    /* 添加操作：key已存在时覆盖value，并返回旧的value */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let (Some(index), _) = self.search(&key) {
+            let slot = self.buckets[index].as_mut().expect("找到的桶中有键值对");
+            return Some(std::mem::replace(&mut slot.value, value));
+        }
+        // 当负载因子超过阈值时，执行扩容
+        if self.policy.should_grow(self.size + 1, self.capacity) {
+            self.resize(self.policy.grown(self.capacity));
+        }
+        self.insert_new(key, value);
+        None
+    }
+
+    /* 插入一个确定不存在的key：沿途遇到更“富”的键值对就与它交换 */
+    fn insert_new(&mut self, key: K, value: V) {
+        let mut index = self.hash_func(&key);
+        let mut carry = Slot {
+            key,
+            value,
+            dist: 0,
+        };
+        loop {
+            match &mut self.buckets[index] {
+                None => {
+                    self.buckets[index] = Some(carry);
+                    self.size += 1;
+                    return;
+                }
+                Some(slot) if slot.dist < carry.dist => std::mem::swap(slot, &mut carry),
+                Some(_) => {}
+            }
+            index = (index + 1) % self.capacity;
+            carry.dist += 1;
+        }
+    }
+
+    /* 删除操作：后移删除，返回被删除的value */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut index = self.search(key).0?;
+        let removed = self.buckets[index].take().expect("找到的桶中有键值对");
+        self.size -= 1;
+        // 把后面不在理想位置上的键值对依次前移一格，填补空出的桶
+        loop {
+            let next = (index + 1) % self.capacity;
+            match self.buckets[next].take() {
+                Some(mut slot) if slot.dist > 0 => {
+                    slot.dist -= 1;
+                    self.buckets[index] = Some(slot);
+                    index = next;
+                }
+                slot => {
+                    self.buckets[next] = slot;
+                    break;
+                }
+            }
+        }
+        // 当负载因子低于缩容阈值时，执行缩容
+        if let Some(capacity) = self.policy.shrunk(self.size, self.capacity) {
+            self.resize(capacity);
+        }
+        Some(removed.value)
+    }
+
+    /* 预留空间：之后再插入additional个键值对都不会触发扩容 */
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.policy.capacity_for(self.size + additional);
+        if capacity > self.capacity {
+            self.resize(capacity);
+        }
+    }
+
+    /* 把容量缩小到恰好能容纳现有键值对 */
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.policy.capacity_for(self.size);
+        if capacity < self.capacity {
+            self.resize(capacity);
+        }
+    }
+
+    /* 扩容或缩容哈希表 */
+    fn resize(&mut self, capacity: usize) {
+        // 暂存哈希表
+        let buckets_tmp = std::mem::take(&mut self.buckets);
+        // 初始化新容量的哈希表
+        self.capacity = capacity;
+        self.resizes += 1;
+        self.buckets = (0..self.capacity).map(|_| None).collect();
+        self.size = 0;
+
+        // 将键值对从原哈希表搬运至新哈希表，key互不相同，无需再查找
+        for slot in buckets_tmp.into_iter().flatten() {
+            self.insert_new(slot.key, slot.value);
+            self.rehash_work += 1;
+        }
+    }
+}
+
+impl<K, V, S> RobinHoodMap<K, V, S> {
+    /* 遍历所有键值对 */
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.buckets
+            .iter()
+            .flatten()
+            .map(|slot| (&slot.key, &slot.value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // 每个键值对的探测长度，即命中查询时需要访问的桶数量
+    fn probe_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.buckets.iter().flatten().map(|slot| slot.dist + 1)
+    }
+
+    /* 最长探测长度，决定了查询的最坏情况 */
+    pub fn max_probe_length(&self) -> usize {
+        self.probe_lengths().max().unwrap_or(0)
+    }
+
+    /* 平均探测长度 */
+    pub fn mean_probe_length(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        self.probe_lengths().sum::<usize>() as f64 / self.size as f64
+    }
+
+    /* 统计快照：直方图统计每个键值对的探测长度 */
+    pub fn stats(&self) -> MapStats {
+        MapStats {
+            size: self.size,
+            capacity: self.capacity,
+            load_factor: self.size as f64 / self.capacity as f64,
+            histogram: MapStats::histogram_of(self.probe_lengths()),
+            max_probe: self.max_probe_length(),
+            tombstones: 0,
+            resizes: self.resizes,
+            rehash_work: self.rehash_work,
+        }
+    }
+
+    /* 探测长度的方差，罗宾汉哈希正是用来减小它的 */
+    pub fn probe_length_variance(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        let mean = self.mean_probe_length();
+        self.probe_lengths()
+            .map(|len| (len as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.size as f64
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> RobinHoodMap<K, V, S> {
+    /* 打印哈希表，同时打印每个键值对的探测距离 */
+    pub fn print(&self) {
+        for bucket in &self.buckets {
+            match bucket {
+                Some(slot) => println!("{:?} -> {:?} (dist {})", slot.key, slot.value, slot.dist),
+                None => println!("NULL"),
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for RobinHoodMap<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for RobinHoodMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,131 @@
+/* 哈希表的统计信息
+    调参时只靠printhash/print打印出全部桶来观察，表一大就看不过来了。每种哈希表都提供stats()，返回一份统计快照：
+        1.键值对数量、容量、负载因子、删除标记数量。
+        2.长度直方图：链式地址统计每个桶的链长，其余哈希表统计每个键值对的探测长度(命中查询时需要检查的位置数)。
+        3.扩容(包括缩容、原地重建与换种子重新哈希)的次数，以及累计搬运的键值对数量，即扩容一共做了多少功。
+    快照可以导出为CSV或JSON，方便把多次运行的结果画成图表。JSON只需要数字、字符串与数组，这里直接手写，不引入依赖。 */
+
+use std::fmt::Write;
+
+/* 统计快照 */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapStats {
+    pub size: usize,           // 键值对数量
+    pub capacity: usize,       // 桶的数量
+    pub load_factor: f64,      // 负载因子
+    pub histogram: Vec<usize>, // 第i格：链长为i的桶数(链式地址)，或探测长度为i的键值对数(其余哈希表)
+    pub max_probe: usize,      // 最长的链或最长的探测长度
+    pub tombstones: usize,     // 删除标记数量
+    pub resizes: usize,        // 扩容、缩容与重建的次数
+    pub rehash_work: usize,    // 扩容时累计搬运的键值对数量
+}
+
+impl MapStats {
+    /* 由长度序列构造直方图 */
+    pub fn histogram_of(lengths: impl Iterator<Item = usize>) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for len in lengths {
+            if histogram.len() <= len {
+                histogram.resize(len + 1, 0);
+            }
+            histogram[len] += 1;
+        }
+        histogram
+    }
+
+    /* 平均长度：按直方图加权 */
+    pub fn mean_probe(&self) -> f64 {
+        let count: usize = self.histogram.iter().sum();
+        if count == 0 {
+            return 0.0;
+        }
+        let total: usize = self
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(len, n)| len * n)
+            .sum();
+        total as f64 / count as f64
+    }
+
+    /* JSON对象，name标识这份快照来自哪个哈希表 */
+    pub fn to_json(&self, name: &str) -> String {
+        let histogram: Vec<String> = self.histogram.iter().map(|n| n.to_string()).collect();
+        format!(
+            "{{\"name\":{},\"size\":{},\"capacity\":{},\"load_factor\":{:.4},\"histogram\":[{}],\
+             \"max_probe\":{},\"mean_probe\":{:.4},\"tombstones\":{},\"resizes\":{},\"rehash_work\":{}}}",
+            json_string(name),
+            self.size,
+            self.capacity,
+            self.load_factor,
+            histogram.join(","),
+            self.max_probe,
+            self.mean_probe(),
+            self.tombstones,
+            self.resizes,
+            self.rehash_work
+        )
+    }
+}
+
+/* 多份快照的汇总CSV，每份一行；直方图各格之间用空格分隔，放在同一列中 */
+pub fn stats_csv(rows: &[(&str, &MapStats)]) -> String {
+    let mut csv = String::from(
+        "name,size,capacity,load_factor,max_probe,mean_probe,tombstones,resizes,rehash_work,histogram\n",
+    );
+    for (name, s) in rows {
+        let histogram: Vec<String> = s.histogram.iter().map(|n| n.to_string()).collect();
+        let _ = writeln!(
+            csv,
+            "{},{},{},{:.4},{},{:.4},{},{},{},{}",
+            csv_field(name),
+            s.size,
+            s.capacity,
+            s.load_factor,
+            s.max_probe,
+            s.mean_probe(),
+            s.tombstones,
+            s.resizes,
+            s.rehash_work,
+            histogram.join(" ")
+        );
+    }
+    csv
+}
+
+/* 多份快照的JSON数组，每份一行 */
+pub fn stats_json(rows: &[(&str, &MapStats)]) -> String {
+    let objects: Vec<String> = rows
+        .iter()
+        .map(|(name, s)| format!("  {}", s.to_json(name)))
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+// 含逗号、引号或换行的CSV字段需要用引号括起来，引号写两遍
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// JSON字符串：转义引号、反斜杠与控制字符
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
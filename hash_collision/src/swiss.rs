@@ -0,0 +1,411 @@
+/* SwissTable风格的分组开放寻址
+        线性探测每检查一个桶都要比较一次key，而key的比较可能很慢(例如长字符串)，桶本身也可能很大，一条缓存行只能放下少数几个。
+        SwissTable(Google Abseil的flat_hash_map，也是Rust标准库HashMap的实现方式)把“桶中有什么”单独存放在一个控制字节数组中：
+            EMPTY   = 0b1000_0000 空桶
+            DELETED = 0b1111_1110 删除标记
+            FULL    = 0b0xxx_xxxx 有键值对，低7位是哈希值的最高7位(h2)
+        哈希值的低位(h1)决定从哪一组开始探测。h1与h2取自哈希值的两端(与Abseil、hashbrown相同)，起始组相同的key的h2仍然各不相同。
+        每16个桶为一组，16个控制字节可以一次性读入，同时与h2比较，只有控制字节匹配的桶才需要真正比较key；
+        组内若有空桶，探测即可结束。各组之间按三角数跳跃，组数是2的幂时能访问到每一组。
+        这里不使用SIMD指令，而是用两个64位整数装下16个控制字节，用位运算同时处理8个字节(SWAR，SIMD within a register)：
+            1.匹配某个字节b：x ^ (b * 0x0101..01) 使匹配的字节变为0，再用“减1后最高位变为1”的技巧找出为0的字节。
+            这一步偶尔会误报(紧跟在0字节之后的0x01字节)，但随后还要比较key，误报不影响正确性。
+            2.匹配空桶：只有EMPTY的最高位为1且第1位为0，x & !(x << 6) 的最高位恰好标记出空桶。
+            3.匹配空桶或删除标记：最高位为1即可。
+        删除时若所在的组中还有空桶，说明没有任何探测路径越过这一组，可以直接标记为空桶而不留删除标记。
+        扩容与缩容由ResizePolicy决定(见resize.rs)，分组探测要求组数是2的幂，因此不接受质数容量，最小容量至少为一组。 */
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::resize::{CapacityKind, ResizePolicy};
+use crate::stats::MapStats;
+use crate::Pair;
+
+const EMPTY: u8 = 0b1000_0000;
+const DELETED: u8 = 0b1111_1110;
+const GROUP_WIDTH: usize = 16;
+
+const LSBS: u64 = 0x0101_0101_0101_0101; // 每个字节的最低位
+const MSBS: u64 = 0x8080_8080_8080_8080; // 每个字节的最高位
+
+/* 一组16个控制字节，用两个64位整数表示 */
+#[derive(Clone, Copy)]
+struct Group([u64; 2]);
+
+impl Group {
+    // 从控制字节数组中读取一组
+    fn load(ctrl: &[u8]) -> Self {
+        let word = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().expect("每个字占8字节"));
+        Group([word(&ctrl[..8]), word(&ctrl[8..16])])
+    }
+
+    // 对两个字分别计算，得到最高位标记了匹配结果的掩码
+    fn mask(self, f: impl Fn(u64) -> u64) -> BitMask {
+        BitMask(f(self.0[0]) as u128 | (f(self.0[1]) as u128) << 64)
+    }
+
+    /* 控制字节等于h2的桶(可能误报) */
+    fn match_byte(self, h2: u8) -> BitMask {
+        self.mask(|x| {
+            let cmp = x ^ (LSBS * h2 as u64);
+            cmp.wrapping_sub(LSBS) & !cmp & MSBS
+        })
+    }
+
+    /* 空桶 */
+    fn match_empty(self) -> BitMask {
+        self.mask(|x| x & !(x << 6) & MSBS)
+    }
+
+    /* 空桶或删除标记 */
+    fn match_empty_or_deleted(self) -> BitMask {
+        self.mask(|x| x & MSBS)
+    }
+}
+
+/* 每个匹配字节的最高位为1的掩码，迭代时依次给出匹配的组内下标 */
+struct BitMask(u128);
+
+impl BitMask {
+    fn any(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1; // 清除最低的1
+        Some(bit / 8)
+    }
+}
+
+/* SwissTable风格的哈希表 */
+pub struct SwissMap<K, V, S = RandomState> {
+    size: usize,                    // 键值对数量
+    tombstones: usize,              // 删除标记数量
+    capacity: usize,                // 桶的数量，是16的倍数且为2的幂
+    policy: ResizePolicy,           // 扩容与缩容策略，扩容阈值按键值对与删除标记一起计算
+    ctrl: Vec<u8>,                  // 控制字节
+    slots: Vec<Option<Pair<K, V>>>, // 桶数组
+    resizes: usize,                 // 扩容、缩容与原地重建的次数
+    rehash_work: usize,             // 累计搬运的键值对数量
+    hasher: S,
+}
+
+impl<K: Hash + Eq, V> SwissMap<K, V, RandomState> {
+    /* 构造方法 */
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> SwissMap<K, V, S> {
+    // 指定哈希算法的构造方法
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            size: 0,
+            tombstones: 0,
+            capacity: GROUP_WIDTH,
+            policy: ResizePolicy::new(7.0 / 8.0).with_min_capacity(GROUP_WIDTH),
+            ctrl: vec![EMPTY; GROUP_WIDTH],
+            slots: (0..GROUP_WIDTH).map(|_| None).collect(),
+            resizes: 0,
+            rehash_work: 0,
+            hasher,
+        }
+    }
+
+    /* 设置扩容与缩容策略，只能在插入键值对之前调用 */
+    pub fn with_policy(mut self, policy: ResizePolicy) -> Self {
+        assert!(self.is_empty(), "只能为空哈希表设置扩容策略");
+        assert!(policy.grow_thres < 1.0, "扩容阈值必须小于1");
+        assert!(
+            policy.capacity_kind == CapacityKind::PowerOfTwo,
+            "分组探测要求容量是2的幂"
+        );
+        assert!(policy.min_capacity >= GROUP_WIDTH, "最小容量至少为一组");
+        self.policy = policy;
+        self.capacity = policy.round_capacity(policy.min_capacity);
+        self.ctrl = vec![EMPTY; self.capacity];
+        self.slots = (0..self.capacity).map(|_| None).collect();
+        self
+    }
+
+    /* 负载因子 */
+    pub fn load_factor(&self) -> f64 {
+        self.size as f64 / self.capacity as f64
+    }
+
+    // 键值对数量
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 判断哈希表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 桶的数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 扩容与缩容策略
+    pub fn policy(&self) -> ResizePolicy {
+        self.policy
+    }
+
+    // 删除标记的数量
+    pub fn tombstones(&self) -> usize {
+        self.tombstones
+    }
+
+    /* 哈希函数：低位h1决定起始组，最高7位h2存入控制字节 */
+    fn hash_func<Q>(&self, key: &Q) -> (usize, u8)
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        (hash as usize, (hash >> 57) as u8)
+    }
+
+    /* 按三角数依次给出要探测的组的起始下标，最多给出组数个 */
+    fn probe_seq(&self, h1: usize) -> impl Iterator<Item = usize> {
+        let groups = self.capacity / GROUP_WIDTH;
+        (0..groups).map(move |i| (h1.wrapping_add(i * (i + 1) / 2) & (groups - 1)) * GROUP_WIDTH)
+    }
+
+    /* 搜索key所在的桶索引 */
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (h1, h2) = self.hash_func(key);
+        for start in self.probe_seq(h1) {
+            let group = Group::load(&self.ctrl[start..]);
+            // 只比较控制字节匹配的桶中的key
+            for i in group.match_byte(h2) {
+                if matches!(&self.slots[start + i], Some(pair) if pair.key.borrow() == key) {
+                    return Some(start + i);
+                }
+            }
+            // 组内有空桶，key不可能在后面的组中
+            if group.match_empty().any() {
+                return None;
+            }
+        }
+        None
+    }
+
+    /* 寻找可以放入新键值对的桶：探测路径上第一个空桶或删除标记 */
+    fn find_insert_slot(&self, h1: usize) -> usize {
+        for start in self.probe_seq(h1) {
+            let group = Group::load(&self.ctrl[start..]);
+            if let Some(i) = group.match_empty_or_deleted().next() {
+                return start + i;
+            }
+        }
+        unreachable!("负载因子小于1，总有空桶")
+    }
+
+    /* 查询操作 */
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_ref().map(|pair| &pair.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_mut().map(|pair| &mut pair.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /* 添加操作：key已存在时覆盖value，并返回旧的value */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(index) = self.find(&key) {
+            let pair = self.slots[index].as_mut().expect("找到的桶中有键值对");
+            return Some(std::mem::replace(&mut pair.value, value));
+        }
+        // 键值对与删除标记一起超过阈值时，扩容或原地重建
+        if self
+            .policy
+            .should_grow(self.size + self.tombstones + 1, self.capacity)
+        {
+            if self.load_factor() > self.policy.grow_thres / 2.0 {
+                self.rehash(self.policy.grown(self.capacity));
+            } else {
+                self.rehash(self.capacity);
+            }
+        }
+        self.insert_new(key, value);
+        None
+    }
+
+    /* 插入一个确定不存在的key */
+    fn insert_new(&mut self, key: K, value: V) {
+        let (h1, h2) = self.hash_func(&key);
+        let index = self.find_insert_slot(h1);
+        if self.ctrl[index] == DELETED {
+            self.tombstones -= 1;
+        }
+        self.ctrl[index] = h2;
+        self.slots[index] = Some(Pair { key, value });
+        self.size += 1;
+    }
+
+    /* 删除操作：返回被删除的value */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        let start = index / GROUP_WIDTH * GROUP_WIDTH;
+        // 组内仍有空桶时，没有探测路径越过这一组，不需要删除标记
+        if Group::load(&self.ctrl[start..]).match_empty().any() {
+            self.ctrl[index] = EMPTY;
+        } else {
+            self.ctrl[index] = DELETED;
+            self.tombstones += 1;
+        }
+        self.size -= 1;
+        let value = self.slots[index].take().map(|pair| pair.value);
+        // 当负载因子低于缩容阈值时，执行缩容
+        if let Some(capacity) = self.policy.shrunk(self.size, self.capacity) {
+            self.rehash(capacity);
+        }
+        value
+    }
+
+    /* 预留空间：之后再插入additional个键值对都不会触发扩容 */
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.policy.capacity_for(self.size + additional);
+        if capacity > self.capacity {
+            self.rehash(capacity);
+        }
+    }
+
+    /* 把容量缩小到恰好能容纳现有键值对，删除标记随之清空 */
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.policy.capacity_for(self.size);
+        if capacity < self.capacity {
+            self.rehash(capacity);
+        }
+    }
+
+    /* 按给定容量重建哈希表，删除标记随之清空 */
+    fn rehash(&mut self, capacity: usize) {
+        let slots_tmp = std::mem::take(&mut self.slots);
+        self.capacity = capacity;
+        self.ctrl = vec![EMPTY; capacity];
+        self.slots = (0..capacity).map(|_| None).collect();
+        self.size = 0;
+        self.tombstones = 0;
+        self.resizes += 1;
+        for pair in slots_tmp.into_iter().flatten() {
+            self.insert_new(pair.key, pair.value);
+            self.rehash_work += 1;
+        }
+    }
+
+    /* 统计快照：直方图统计查询每个键值对时需要探测的组数 */
+    pub fn stats(&self) -> MapStats {
+        let groups_probed = self.slots.iter().enumerate().filter_map(|(index, pair)| {
+            let (h1, _) = self.hash_func(&pair.as_ref()?.key);
+            let start = index / GROUP_WIDTH * GROUP_WIDTH;
+            self.probe_seq(h1).position(|s| s == start).map(|i| i + 1)
+        });
+        let histogram = MapStats::histogram_of(groups_probed);
+        MapStats {
+            size: self.size,
+            capacity: self.capacity,
+            load_factor: self.load_factor(),
+            max_probe: histogram.len().saturating_sub(1),
+            histogram,
+            tombstones: self.tombstones,
+            resizes: self.resizes,
+            rehash_work: self.rehash_work,
+        }
+    }
+}
+
+impl<K, V, S> SwissMap<K, V, S> {
+    /* 遍历所有键值对 */
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots
+            .iter()
+            .flatten()
+            .map(|pair| (&pair.key, &pair.value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> SwissMap<K, V, S> {
+    /* 打印哈希表：每行一组，先打印控制字节 */
+    pub fn print(&self) {
+        for (ctrl, slots) in self
+            .ctrl
+            .chunks(GROUP_WIDTH)
+            .zip(self.slots.chunks(GROUP_WIDTH))
+        {
+            let bytes: Vec<String> = ctrl
+                .iter()
+                .map(|&c| match c {
+                    EMPTY => "E".to_string(),
+                    DELETED => "D".to_string(),
+                    h2 => format!("{:02x}", h2),
+                })
+                .collect();
+            println!("{}", bytes.join(" "));
+            for pair in slots.iter().flatten() {
+                println!("    {:?} -> {:?}", pair.key, pair.value);
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for SwissMap<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for SwissMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
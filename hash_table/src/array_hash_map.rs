@@ -0,0 +1,251 @@
+/* 基于数组实现的哈希表
+    数组中的每个空位称为桶(bucket)，每个桶可存储一个键值对。查询时通过哈希函数找到key对应的桶，在桶中获取value。
+        index = hash(key) % capacity
+    这个最简单的实现中每个桶只能放一个键值对，两个不同的key落到同一个桶时就发生了哈希冲突。
+    这里不掩盖冲突：put在冲突时返回错误，把被拒绝的键值对交还给调用者。解决冲突的办法见hash_collision。 */
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+/* 键值对 */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+/* 哈希冲突：目标桶已被另一个key占用，携带未能插入的键值对 */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collision<K, V> {
+    pub key: K,
+    pub value: V,
+    pub index: usize, // 发生冲突的桶索引
+}
+
+impl<K: fmt::Debug, V> fmt::Display for Collision<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key {:?} 与桶 {} 中已有的key发生哈希冲突", self.key, self.index)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> std::error::Error for Collision<K, V> {}
+
+/* 基于数组实现的哈希表 */
+pub struct ArrayHashMap<K, V, S = RandomState> {
+    buckets: Vec<Option<Pair<K, V>>>,
+    size: usize,
+    hasher: S,
+}
+
+impl<K: Hash + Eq, V> ArrayHashMap<K, V, RandomState> {
+    pub fn new() -> Self {
+        // 初始化数组，包含100个桶
+        Self::with_buckets(100)
+    }
+
+    // 指定桶数量的构造方法
+    pub fn with_buckets(buckets: usize) -> Self {
+        Self::with_buckets_and_hasher(buckets, RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> ArrayHashMap<K, V, S> {
+    // 指定桶数量与哈希算法的构造方法
+    pub fn with_buckets_and_hasher(buckets: usize, hasher: S) -> Self {
+        assert!(buckets > 0, "桶的数量必须大于0");
+        Self {
+            buckets: (0..buckets).map(|_| None).collect(),
+            size: 0,
+            hasher,
+        }
+    }
+
+    /* 哈希函数 */
+    fn hash_func<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        (self.hasher.hash_one(key) % self.buckets.len() as u64) as usize
+    }
+
+    // 键值对数量
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // 判断哈希表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 桶的数量
+    pub fn capacity(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /* 负载因子 */
+    pub fn load_factor(&self) -> f64 {
+        self.size as f64 / self.buckets.len() as f64
+    }
+
+    // 桶中的键值对恰好是要找的key时返回它
+    fn find<Q>(&self, key: &Q) -> Option<&Pair<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.hash_func(key);
+        self.buckets[index]
+            .as_ref()
+            .filter(|pair| pair.key.borrow() == key)
+    }
+
+    /* 查询操作 */
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|pair| &pair.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.hash_func(key);
+        self.buckets[index]
+            .as_mut()
+            .filter(|pair| pair.key.borrow() == key)
+            .map(|pair| &mut pair.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /* 添加操作
+        桶为空则插入并返回Ok(None)；桶中是同一个key则覆盖并返回Ok(Some(旧value))；
+        桶被其他key占用则不做任何修改，返回Err(Collision)。 */
+    pub fn put(&mut self, key: K, value: V) -> Result<Option<V>, Collision<K, V>> {
+        let index = self.hash_func(&key);
+        match &mut self.buckets[index] {
+            Some(pair) if pair.key == key => Ok(Some(std::mem::replace(&mut pair.value, value))),
+            Some(_) => Err(Collision { key, value, index }),
+            slot @ None => {
+                *slot = Some(Pair { key, value });
+                self.size += 1;
+                Ok(None)
+            }
+        }
+    }
+
+    /* 删除操作：只有桶中恰好是该key时才删除，返回被删除的value */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.hash_func(key);
+        let slot = &mut self.buckets[index];
+        if slot.as_ref().is_some_and(|pair| pair.key.borrow() == key) {
+            self.size -= 1;
+            // 置为None，代表删除
+            return slot.take().map(|pair| pair.value);
+        }
+        None
+    }
+
+    // 清空哈希表
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(|slot| *slot = None);
+        self.size = 0;
+    }
+}
+
+impl<K, V, S> ArrayHashMap<K, V, S> {
+    /* 遍历键值对 key -> value */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            buckets: self.buckets.iter(),
+        }
+    }
+
+    /* 单独遍历key */
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /* 单独遍历value */
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /* 获取所有键值对 */
+    pub fn entry_set(&self) -> Vec<&Pair<K, V>> {
+        self.buckets.iter().filter_map(|pair| pair.as_ref()).collect()
+    }
+
+    /* 获取所有键 */
+    pub fn key_set(&self) -> Vec<&K> {
+        self.keys().collect()
+    }
+
+    /* 获取所有值 */
+    pub fn value_set(&self) -> Vec<&V> {
+        self.values().collect()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> ArrayHashMap<K, V, S> {
+    /* 打印哈希表 */
+    pub fn println(&self) {
+        for pair in self.entry_set() {
+            println!("{:?} -> {:?}", pair.key, pair.value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for ArrayHashMap<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for ArrayHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/* 按桶的顺序遍历键值对 */
+pub struct Iter<'a, K, V> {
+    buckets: std::slice::Iter<'a, Option<Pair<K, V>>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buckets
+            .by_ref()
+            .find_map(|slot| slot.as_ref().map(|pair| (&pair.key, &pair.value)))
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a ArrayHashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
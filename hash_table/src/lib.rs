@@ -0,0 +1,2 @@
+/* 哈希表的实现 */
+pub mod array_hash_map;
@@ -1,6 +1,9 @@
 /* 哈希表，又称“散列表”，它通过建立键key和值value之间的映射，实现高效的元素查找。具体而言，我们向哈希表中输入一个键key，则可以在O(1)事件内获取到对应的值value */
 
-use std::{collections::HashMap, hash::Hash};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+use hash_table::array_hash_map::{ArrayHashMap, Collision};
 
 fn main() {
     /* 初始化哈希表 */
@@ -39,77 +42,131 @@ fn main() {
     for value in map.values(){
         println!("{value}");
     }
-}
 
-/* 键值对 */
-#[derive(Debug, Clone, PartialEq)]
-pub struct Pair{
-    pub key: i32,
-    pub value: String,
-}
+    /* 基于数组实现的哈希表 */
+    // 使用index = key % 100作为哈希函数，这5个key各占一个桶
+    let mut array_map: ArrayHashMap<i32, String, Identity> =
+        ArrayHashMap::with_buckets_and_hasher(100, Identity::default());
+    for (key, value) in [(12836, "小哈"), (15937, "小锣"), (16750, "小算"), (13276, "小法"), (10583, "小鸭")] {
+        if let Err(err) = array_map.put(key, value.to_string()) {
+            println!("{}，{} 未能插入", err, err.value);
+        }
+    }
+    array_map.println();
 
-/* 基于数组实现的哈希表 */
-pub struct ArrayHashMap{
-    buckets: Vec<Option<Pair>>,
+    check_array_hash_map();
 }
 
-impl ArrayHashMap {
-    pub fn new() -> ArrayHashMap {
-        // 初始化数组，包含100个桶
-        Self{
-            buckets: vec![None; 100],
+/* 直接把整数key当作哈希值，于是 index = key % capacity，哪些key会冲突可以事先算出来 */
+#[derive(Default)]
+struct IdentityHasher(u64);
+
+impl Hasher for IdentityHasher {
+    // 其他类型的key：逐字节移入哈希值
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0 << 8 | byte as u64;
         }
     }
 
-    /* 哈希函数 */
-    fn hash_func(&self, key: i32) -> usize{
-        key as usize % 100
+    fn write_i32(&mut self, n: i32) {
+        self.0 = n as u64;
     }
 
-    /* 查询操作 */
-    pub fn get(&self, key: i32) -> Option<&String> {
-        let index = self.hash_func(key);
-        self.buckets[index].as_ref().map(|pair| &pair.value)
+    fn write_i64(&mut self, n: i64) {
+        self.0 = n as u64;
     }
 
-    /* 添加操作 */
-    pub fn put(&mut self, key: i32, value: &str) {
-        let index = self.hash_func(key);
-        self.buckets[index] = Some(Pair { key, value: value.to_string() })
+    fn finish(&self) -> u64 {
+        self.0
     }
+}
 
-    /* 删除操作 */
-    pub fn remove(&mut self, key: i32) {
-        let index = self.hash_func(key);
-        // 置为None，代表删除
-        self.buckets[index] = None;
+type Identity = BuildHasherDefault<IdentityHasher>;
+
+/* ArrayHashMap的检查：负数key、冲突检测、删除只删除对应的key，以及keys/values/iter视图 */
+fn check_array_hash_map() {
+    // 负数key与正数key一样可以存取。负数按补码当作u64取余，这几个key分别落在615、516、968、0、1、647号桶，互不冲突
+    let mut map: ArrayHashMap<i32, String, Identity> =
+        ArrayHashMap::with_buckets_and_hasher(1000, Identity::default());
+    for key in [-1, -100, i32::MIN, 0, 1, i32::MAX] {
+        match map.put(key, key.to_string()) {
+            Ok(old) => assert_eq!(old, None),
+            Err(Collision { key: k, index, .. }) => panic!("{} 在桶 {} 冲突", k, index),
+        }
     }
-
-    /* 获取所有键值对 */
-    pub fn entry_set(&self) -> Vec<&Pair> {
-        self.buckets
-            .iter()
-            .filter_map(|pair| pair.as_ref())
-            .collect()
+    for key in [-1, -100, i32::MIN, 0, 1, i32::MAX] {
+        assert_eq!(map.get(&key), Some(&key.to_string()));
     }
-
-    /* 获取所有键 */
-    pub fn key_set(&self) -> Vec<&String> {
-        self.buckets
-            .iter()
-            .filter_map(|pair| pair.as_ref())
-            .map(|pair| &pair.value)
-            .collect()
+    assert_eq!(map.get(&-2), None);
+    assert_eq!(map.put(-1, "负一".to_string()), Ok(Some("-1".to_string())));
+    assert_eq!(map.remove(&-1), Some("负一".to_string()));
+    assert_eq!(map.remove(&-1), None);
+    assert_eq!(map.size(), 5);
+
+    // 64个桶中依次插入-200..200：只有每个桶的第一个key能放进去，其余336个key都与相差64整数倍的key冲突，
+    // 冲突的键值对被原样退回，原有的键值对不受影响
+    let mut map: ArrayHashMap<i64, i64, Identity> =
+        ArrayHashMap::with_buckets_and_hasher(64, Identity::default());
+    let mut model: HashMap<i64, i64> = HashMap::new();
+    let mut collisions = 0;
+    for key in -200..200 {
+        match map.put(key, key * 10) {
+            Ok(old) => assert_eq!(old, model.insert(key, key * 10)),
+            Err(err) => {
+                collisions += 1;
+                assert_eq!((err.key, err.value), (key, key * 10));
+                assert_eq!(err.index, key.rem_euclid(64) as usize);
+                assert!(!map.contains_key(&key));
+            }
+        }
     }
-
-    /* 打印哈希表 */
-    pub fn println(&self) {
-       for pair in self.entry_set(){
-            println!("{} -> {}", pair.key, pair.value);
-       }
+    assert_eq!(collisions, 400 - 64);
+    assert_eq!(map.size(), 64);
+    assert_eq!(map.size(), model.len());
+    assert!((-200..-136).all(|key| model.contains_key(&key)));
+    assert_eq!(map.load_factor(), 1.0);
+
+    // 删除一个不在表中、但与表中某个key同桶的key，不能误删后者
+    for key in -200..200 {
+        if !model.contains_key(&key) {
+            assert_eq!(map.remove(&key), None);
+        }
+    }
+    assert_eq!(map.size(), model.len());
+    for (key, value) in &model {
+        assert_eq!(map.get(key), Some(value));
     }
+
+    // keys、values与iter返回的是同一组键值对
+    let keys: HashSet<i64> = map.keys().copied().collect();
+    assert_eq!(keys, model.keys().copied().collect());
+    let mut values: Vec<i64> = map.values().copied().collect();
+    let mut expected: Vec<i64> = model.values().copied().collect();
+    values.sort();
+    expected.sort();
+    assert_eq!(values, expected);
+    assert!(map.iter().all(|(key, value)| *value == key * 10));
+    assert_eq!(map.key_set().len(), map.size());
+    assert_eq!(map.entry_set().len(), map.value_set().len());
+
+    // get_mut与clear
+    let key = *map.keys().next().unwrap();
+    *map.get_mut(&key).unwrap() += 1;
+    assert_eq!(map.get(&key), Some(&(key * 10 + 1)));
+    map.clear();
+    assert!(map.is_empty() && map.iter().next().is_none());
+
+    // 借用形式的key：String键可以用&str查询
+    let mut names: ArrayHashMap<String, u32> = ArrayHashMap::with_buckets(16);
+    names.put("小哈".to_string(), 1).unwrap();
+    assert_eq!(names.get("小哈"), Some(&1));
+    println!("ArrayHashMap的冲突检测与遍历符合预期");
 }
 
+/* 基于数组实现的哈希表 */
+// 见 array_hash_map.rs
+
 /* 从本质上来看，哈希函数的作用是将所有的key构成的输入空间映射到数组所有索引构成的输出空间，而输入空间往往大于输出空间，
     因此，理论上存在“多个输入对应相同输出”的情况。
     我们将多个输入对应同一输出的情况称为哈希冲突。 */
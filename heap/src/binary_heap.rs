@@ -0,0 +1,341 @@
+/* 二叉堆
+    堆是一棵满足特定条件的完全二叉树：大顶堆中任意节点的值都不小于其子节点的值，小顶堆则相反。
+    完全二叉树非常适合用数组来表示：按层序遍历的顺序把节点存入数组，给定索引i，
+        左子节点的索引为2i+1，右子节点的索引为2i+2，父节点的索引为(i-1)/2(向下整除)。
+    于是堆不需要任何指针，入堆和出堆都只需沿着一条从根到叶的路径交换节点，时间复杂度为O(log n)。
+    “谁在堆顶”由比较器决定：比较结果为Greater的元素优先级更高，更靠近堆顶。 */
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/* 比较器：compare(a, b)返回Greater表示a的优先级高于b */
+pub trait Comparator<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/* 大顶堆：值越大优先级越高 */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxComparator;
+
+impl<T: Ord> Comparator<T> for MaxComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/* 小顶堆：值越小优先级越高 */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinComparator;
+
+impl<T: Ord> Comparator<T> for MinComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/* 自定义比较器，包装一个比较函数 */
+#[derive(Clone, Copy)]
+pub struct FnComparator<F>(pub F);
+
+impl<T, F: Fn(&T, &T) -> Ordering> Comparator<T> for FnComparator<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+/* 基于数组的二叉堆 */
+#[derive(Clone)]
+pub struct BinaryHeap<T, C: Comparator<T> = MaxComparator> {
+    data: Vec<T>, // 按层序存储的完全二叉树
+    cmp: C,
+}
+
+/* 获取左子节点的索引 */
+fn left(i: usize) -> usize {
+    2 * i + 1
+}
+
+/* 获取右子节点的索引 */
+fn right(i: usize) -> usize {
+    2 * i + 2
+}
+
+/* 获取父节点的索引 */
+fn parent(i: usize) -> usize {
+    (i - 1) / 2
+}
+
+impl<T: Ord> BinaryHeap<T, MaxComparator> {
+    // 大顶堆
+    pub fn new_max() -> Self {
+        Self::with_comparator(MaxComparator)
+    }
+}
+
+impl<T: Ord> BinaryHeap<T, MinComparator> {
+    // 小顶堆
+    pub fn new_min() -> Self {
+        Self::with_comparator(MinComparator)
+    }
+}
+
+impl<T, C: Comparator<T> + Default> BinaryHeap<T, C> {
+    // 构造函数
+    pub fn new() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Comparator<T>> BinaryHeap<T, C> {
+    // 指定比较器的构造函数
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            data: Vec::new(),
+            cmp,
+        }
+    }
+
+    /* 建堆：从最后一个非叶节点开始，自底向上对每个节点执行从顶至底堆化，时间复杂度为O(n) */
+    pub fn from_vec(data: Vec<T>, cmp: C) -> Self {
+        let mut heap = Self { data, cmp };
+        heap.rebuild();
+        heap
+    }
+
+    fn rebuild(&mut self) {
+        if self.data.len() < 2 {
+            return;
+        }
+        for i in (0..=parent(self.data.len() - 1)).rev() {
+            self.sift_down(i, self.data.len());
+        }
+    }
+
+    // 判断a处的元素优先级是否高于b处的元素
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.cmp.compare(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+
+    /* 从节点i开始，从底至顶堆化 */
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let p = parent(i);
+            // 当节点优先级不高于父节点时，无须继续堆化
+            if !self.higher(i, p) {
+                break;
+            }
+            self.data.swap(i, p);
+            i = p;
+        }
+    }
+
+    /* 从节点i开始，在[0, len)范围内从顶至底堆化 */
+    fn sift_down(&mut self, mut i: usize, len: usize) {
+        loop {
+            // 找出节点i、l、r中优先级最高的节点，记为top
+            let (l, r) = (left(i), right(i));
+            let mut top = i;
+            if l < len && self.higher(l, top) {
+                top = l;
+            }
+            if r < len && self.higher(r, top) {
+                top = r;
+            }
+            // 若节点i最高或索引l、r越界，则无须继续堆化
+            if top == i {
+                break;
+            }
+            self.data.swap(i, top);
+            i = top;
+        }
+    }
+
+    // 获取堆的大小
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    // 判断堆是否为空
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    // 获取比较器
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /* 元素入堆 */
+    pub fn push(&mut self, elem: T) {
+        // 添加节点，再从底至顶堆化
+        self.data.push(elem);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    /* 堆顶元素出堆 */
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        // 交换根节点与最右叶节点，删除最右叶节点，再从顶至底堆化
+        let last = self.data.len() - 1;
+        self.data.swap(0, last);
+        let elem = self.data.pop();
+        self.sift_down(0, self.data.len());
+        elem
+    }
+
+    /* 访问堆顶元素 */
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /* 修改堆顶元素，返回的守卫被丢弃时自动重新堆化 */
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    /* 合并另一个堆：另一个堆较大时直接拼接后重新建堆，否则逐个入堆 */
+    pub fn merge(&mut self, other: BinaryHeap<T, C>) {
+        let other = other.data;
+        if other.len() > self.data.len() / 2 {
+            self.data.extend(other);
+            self.rebuild();
+        } else {
+            for elem in other {
+                self.push(elem);
+            }
+        }
+    }
+
+    /* 堆排序：按优先级从低到高返回所有元素(大顶堆为升序，小顶堆为降序) */
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        // 反复把堆顶交换到未排序区间的末尾，再对剩余区间堆化
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    // 以层序返回底层数组
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    // 按层序遍历，顺序不代表优先级
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    // 清空堆
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<T, C: Comparator<T> + Default> Default for BinaryHeap<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for BinaryHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for BinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T: fmt::Debug, C: Comparator<T>> fmt::Debug for BinaryHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+/* peek_mut返回的守卫：可以像&mut T一样修改堆顶，被丢弃时从堆顶开始向下堆化 */
+pub struct PeekMut<'a, T, C: Comparator<T>> {
+    heap: &'a mut BinaryHeap<T, C>,
+}
+
+impl<T, C: Comparator<T>> PeekMut<'_, T, C> {
+    // 直接弹出堆顶元素
+    pub fn pop(this: Self) -> T {
+        let mut this = std::mem::ManuallyDrop::new(this);
+        this.heap.pop().expect("PeekMut只在堆非空时存在")
+    }
+}
+
+impl<T, C: Comparator<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, C: Comparator<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T, C: Comparator<T>> Drop for PeekMut<'_, T, C> {
+    fn drop(&mut self) {
+        let len = self.heap.data.len();
+        self.heap.sift_down(0, len);
+    }
+}
+
+/* Top-k问题：返回最大的k个元素(降序)。
+    维护一个大小为k的小顶堆，堆顶是当前第k大的元素；新元素比堆顶大就替换堆顶，时间复杂度为O(n log k)。 */
+pub fn top_k<T: Ord, I: IntoIterator<Item = T>>(iter: I, k: usize) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap: BinaryHeap<T, MinComparator> = BinaryHeap::new_min();
+    for elem in iter {
+        if heap.size() < k {
+            heap.push(elem);
+        } else if let Some(mut top) = heap.peek_mut() {
+            if elem > *top {
+                *top = elem;
+            }
+        }
+    }
+    // 小顶堆的into_sorted_vec按优先级从低到高排列，即值从大到小
+    heap.into_sorted_vec()
+}
+
+/* 多路归并：把k个升序序列合并为一个升序序列。
+    小顶堆中保存每个序列当前的首元素，每次弹出最小者并补入它所在序列的下一个元素，时间复杂度为O(n log k)。
+    值相同时按序列编号排序，保证结果稳定。 */
+pub fn k_way_merge<T: Ord, I: IntoIterator<Item = T>>(sources: Vec<I>) -> Vec<T> {
+    let mut iters: Vec<I::IntoIter> = sources.into_iter().map(|s| s.into_iter()).collect();
+    let mut heap: BinaryHeap<(T, usize), MinComparator> = BinaryHeap::new_min();
+    for (i, iter) in iters.iter_mut().enumerate() {
+        if let Some(elem) = iter.next() {
+            heap.push((elem, i));
+        }
+    }
+    let mut res = Vec::new();
+    while let Some((elem, i)) = heap.pop() {
+        res.push(elem);
+        if let Some(next) = iters[i].next() {
+            heap.push((next, i));
+        }
+    }
+    res
+}
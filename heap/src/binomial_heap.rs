@@ -0,0 +1,218 @@
+/* 二项堆
+    k阶二项树B_k由两棵B_(k-1)连接而成：一棵的根成为另一棵的根的子节点。B_k恰好有2^k个节点，根有k个子节点，
+    依次是B_0, B_1, ..., B_(k-1)。二项堆是一组阶数互不相同的二项树，每棵树都满足小顶堆性质。
+    n个元素的二项堆中有哪些阶的树，恰好对应n的二进制表示中哪些位是1，因此：
+        1.合并：像二进制加法一样，从低阶到高阶把同阶的树两两连接，产生“进位”，O(log n)。
+        2.插入：与只有一个B_0的堆合并。
+        3.删除最小值：在O(log n)个根中找到最小者，删除它后它的子树恰好组成另一个二项堆，再合并回来。
+        4.decrease_key：降低值后与父节点交换，直到不小于父节点，树高为O(log n)。
+    交换只交换节点中的元素，因此元素单独存放，并记录自己所在的节点，句柄指向元素而不是节点。 */
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use crate::mergeable_heap::{DecreaseKey, DecreaseKeyError, MergeableHeap};
+
+type NodeRef<T> = Rc<RefCell<BinomialNode<T>>>;
+type ItemRef<T> = Rc<RefCell<Item<T>>>;
+
+/* 元素及其当前所在的节点 */
+struct Item<T> {
+    val: T,
+    node: Weak<RefCell<BinomialNode<T>>>,
+}
+
+/* 二项树节点，阶数即子节点数量 */
+struct BinomialNode<T> {
+    item: ItemRef<T>,
+    parent: Option<Weak<RefCell<BinomialNode<T>>>>,
+    children: Vec<NodeRef<T>>, // children[i]是i阶二项树
+}
+
+/* 二项堆 */
+pub struct BinomialHeap<T> {
+    trees: Vec<Option<NodeRef<T>>>, // trees[k]是k阶二项树(若存在)
+    len: usize,
+}
+
+/* 二项堆的元素句柄 */
+pub struct BinomialHandle<T>(Weak<RefCell<Item<T>>>);
+
+impl<T> Clone for BinomialHandle<T> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+// 判断节点a的元素是否小于节点b的元素
+fn less<T: Ord>(a: &NodeRef<T>, b: &NodeRef<T>) -> bool {
+    a.borrow().item.borrow().val < b.borrow().item.borrow().val
+}
+
+/* 连接两棵同阶的树：根较大的树成为另一棵树的最高阶子树 */
+fn link<T: Ord>(a: NodeRef<T>, b: NodeRef<T>) -> NodeRef<T> {
+    let (parent, child) = if less(&b, &a) { (b, a) } else { (a, b) };
+    child.borrow_mut().parent = Some(Rc::downgrade(&parent));
+    parent.borrow_mut().children.push(child);
+    parent
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    /* 二进制加法：把一组按阶数排列的树并入堆 */
+    fn merge_trees(&mut self, mut others: Vec<Option<NodeRef<T>>>) {
+        let len = self.trees.len().max(others.len());
+        self.trees.resize_with(len, || None);
+        others.resize_with(len, || None);
+        let mut carry: Option<NodeRef<T>> = None;
+        for (k, other) in others.into_iter().enumerate() {
+            let mut same: Vec<NodeRef<T>> = [self.trees[k].take(), other, carry.take()]
+                .into_iter()
+                .flatten()
+                .collect();
+            // 同阶的树有3棵时留下一棵，另外两棵连接后进位
+            if same.len() == 3 {
+                self.trees[k] = same.pop();
+            }
+            if same.len() == 2 {
+                let b = same.pop().expect("有两棵树");
+                let a = same.pop().expect("有两棵树");
+                carry = Some(link(a, b));
+            } else {
+                self.trees[k] = same.pop();
+            }
+        }
+        if carry.is_some() {
+            self.trees.push(carry);
+        }
+    }
+
+    // 最小元素所在树的阶数
+    fn min_order(&self) -> Option<usize> {
+        let mut min: Option<usize> = None;
+        for (k, tree) in self.trees.iter().enumerate() {
+            if let Some(tree) = tree {
+                if min.is_none_or(|m| less(tree, self.trees[m].as_ref().expect("已记录的树存在"))) {
+                    min = Some(k);
+                }
+            }
+        }
+        min
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for BinomialHeap<T> {
+    fn new() -> Self {
+        Self {
+            trees: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.len
+    }
+
+    fn insert(&mut self, val: T) {
+        self.insert_with_handle(val);
+    }
+
+    fn peek_min(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let k = self.min_order()?;
+        let tree = self.trees[k].as_ref().expect("最小值所在的树存在");
+        let val = tree.borrow().item.borrow().val.clone();
+        Some(val)
+    }
+
+    fn extract_min(&mut self) -> Option<T> {
+        let k = self.min_order()?;
+        let root = self.trees[k].take().expect("最小值所在的树存在");
+        while self.trees.last().is_some_and(|t| t.is_none()) {
+            self.trees.pop();
+        }
+        let node = Rc::try_unwrap(root)
+            .ok()
+            .expect("根节点不应被其他强引用持有")
+            .into_inner();
+        // 子树依次是0阶到k-1阶，恰好组成另一个二项堆
+        let children: Vec<Option<NodeRef<T>>> = node
+            .children
+            .into_iter()
+            .map(|child| {
+                child.borrow_mut().parent = None;
+                Some(child)
+            })
+            .collect();
+        self.merge_trees(children);
+        self.len -= 1;
+        let item = Rc::try_unwrap(node.item)
+            .ok()
+            .expect("元素只被所在节点强引用")
+            .into_inner();
+        Some(item.val)
+    }
+
+    fn meld(&mut self, mut other: Self) {
+        let trees = std::mem::take(&mut other.trees);
+        self.merge_trees(trees);
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl<T: Ord> DecreaseKey<T> for BinomialHeap<T> {
+    type Handle = BinomialHandle<T>;
+
+    fn insert_with_handle(&mut self, val: T) -> BinomialHandle<T> {
+        let item = Rc::new(RefCell::new(Item {
+            val,
+            node: Weak::new(),
+        }));
+        let node = Rc::new(RefCell::new(BinomialNode {
+            item: Rc::clone(&item),
+            parent: None,
+            children: Vec::new(),
+        }));
+        item.borrow_mut().node = Rc::downgrade(&node);
+        self.merge_trees(vec![Some(node)]);
+        self.len += 1;
+        BinomialHandle(Rc::downgrade(&item))
+    }
+
+    fn decrease_key(&mut self, handle: &BinomialHandle<T>, val: T) -> Result<(), DecreaseKeyError> {
+        let item = handle.0.upgrade().ok_or(DecreaseKeyError::Removed)?;
+        if val > item.borrow().val {
+            return Err(DecreaseKeyError::KeyIncreased);
+        }
+        item.borrow_mut().val = val;
+        let mut node = item.borrow().node.upgrade().expect("元素所在的节点存在");
+        drop(item);
+        // 不断与父节点交换元素，直到不小于父节点
+        loop {
+            let parent = node.borrow().parent.as_ref().and_then(Weak::upgrade);
+            let Some(parent) = parent else {
+                break;
+            };
+            if !less(&node, &parent) {
+                break;
+            }
+            {
+                let mut n = node.borrow_mut();
+                let mut p = parent.borrow_mut();
+                std::mem::swap(&mut n.item, &mut p.item);
+                n.item.borrow_mut().node = Rc::downgrade(&node);
+                p.item.borrow_mut().node = Rc::downgrade(&parent);
+            }
+            node = parent;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Ord> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,253 @@
+/* 索引堆(可修改优先级的优先队列)
+    普通的堆只能访问堆顶，无法找到某个元素在数组中的位置。索引堆额外用哈希表记录“键 -> 数组下标”，
+    每次交换节点时同步更新两个键的下标，于是可以在O(log n)时间内修改或删除任意键。
+    Dijkstra、Prim等图算法需要不断降低节点的距离(decrease_key)，使用索引堆可以避免重复入堆。
+
+    d叉堆：每个节点有d个子节点，给定索引i，
+        第j个子节点的索引为d*i+j(1 <= j <= d)，父节点的索引为(i-1)/d。
+    d越大树越矮，上浮(入堆、decrease_key)需要的比较越少；但下沉(出堆)每层要在d个子节点中找最优者。
+    d个兄弟节点在数组中是连续的，4叉或8叉时它们往往落在同一条缓存行里，因此下沉的实际代价比比较次数看起来要低。 */
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::binary_heap::{Comparator, MinComparator};
+
+/* 堆中的一个条目 */
+struct Entry<K, P> {
+    key: K,
+    priority: P,
+}
+
+/* 索引堆，默认是小顶堆(优先级的值越小越先出堆) */
+pub struct IndexedHeap<K, P, C: Comparator<P> = MinComparator> {
+    entries: Vec<Entry<K, P>>,    // 按层序存储的完全d叉树
+    positions: HashMap<K, usize>, // 键 -> entries中的下标
+    arity: usize,                 // 每个节点的子节点数量
+    cmp: C,
+}
+
+impl<K: Hash + Eq + Clone, P, C: Comparator<P> + Default> IndexedHeap<K, P, C> {
+    // 构造函数，默认为二叉堆
+    pub fn new() -> Self {
+        Self::with_arity(2)
+    }
+
+    // 指定分支数的构造函数
+    pub fn with_arity(arity: usize) -> Self {
+        Self::with_arity_and_comparator(arity, C::default())
+    }
+}
+
+impl<K: Hash + Eq + Clone, P, C: Comparator<P>> IndexedHeap<K, P, C> {
+    // 指定分支数与比较器的构造函数
+    pub fn with_arity_and_comparator(arity: usize, cmp: C) -> Self {
+        assert!(arity >= 2, "堆的分支数至少为2");
+        Self {
+            entries: Vec::new(),
+            positions: HashMap::new(),
+            arity,
+            cmp,
+        }
+    }
+
+    // 获取分支数
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    // 获取堆的大小
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    // 判断堆是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // 判断键是否在堆中
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.contains_key(key)
+    }
+
+    // 获取键当前的优先级
+    pub fn priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.get(key).map(|&i| &self.entries[i].priority)
+    }
+
+    /* 访问堆顶 */
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.entries.first().map(|e| (&e.key, &e.priority))
+    }
+
+    /* 入堆：键已存在时更新其优先级，并返回旧的优先级 */
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&i) = self.positions.get(&key) {
+            return Some(self.replace_at(i, priority));
+        }
+        let i = self.entries.len();
+        self.positions.insert(key.clone(), i);
+        self.entries.push(Entry { key, priority });
+        self.sift_up(i);
+        None
+    }
+
+    /* 堆顶出堆 */
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let entry = self.remove_at(0);
+        Some((entry.key, entry.priority))
+    }
+
+    /* 修改键的优先级，返回旧的优先级；键不存在时返回None */
+    pub fn update_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.positions.get(key)?;
+        Some(self.replace_at(i, priority))
+    }
+
+    /* 只在新优先级更高时才修改(小顶堆中即降低键值)，返回是否修改成功 */
+    pub fn decrease_key<Q>(&mut self, key: &Q, priority: P) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&i) = self.positions.get(key) else {
+            return false;
+        };
+        if !self.cmp.compare(&priority, &self.entries[i].priority).is_gt() {
+            return false;
+        }
+        self.entries[i].priority = priority;
+        self.sift_up(i);
+        true
+    }
+
+    /* 删除任意键，返回它的优先级 */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.positions.get(key)?;
+        Some(self.remove_at(i).priority)
+    }
+
+    // 清空堆
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.positions.clear();
+    }
+
+    // 按层序遍历所有键与优先级，顺序不代表优先级
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &P)> {
+        self.entries.iter().map(|e| (&e.key, &e.priority))
+    }
+
+    // 替换下标i处的优先级，并根据新旧优先级决定上浮还是下沉
+    fn replace_at(&mut self, i: usize, priority: P) -> P {
+        let old = std::mem::replace(&mut self.entries[i].priority, priority);
+        if self.cmp.compare(&self.entries[i].priority, &old).is_gt() {
+            self.sift_up(i);
+        } else {
+            self.sift_down(i);
+        }
+        old
+    }
+
+    // 删除下标i处的条目：与最后一个条目交换后删除，再修复被换上来的条目
+    fn remove_at(&mut self, i: usize) -> Entry<K, P> {
+        let last = self.entries.len() - 1;
+        self.swap(i, last);
+        let entry = self.entries.pop().expect("下标在堆的范围内");
+        self.positions.remove(&entry.key);
+        if i < self.entries.len() {
+            // 换上来的条目可能比原位置的父节点更优，也可能比子节点更差
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        entry
+    }
+
+    // 交换两个条目，并同步更新它们的下标
+    fn swap(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        self.entries.swap(a, b);
+        for i in [a, b] {
+            if let Some(pos) = self.positions.get_mut(&self.entries[i].key) {
+                *pos = i;
+            }
+        }
+    }
+
+    // 判断a处的条目优先级是否高于b处的条目
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.cmp
+            .compare(&self.entries[a].priority, &self.entries[b].priority)
+            .is_gt()
+    }
+
+    /* 从节点i开始，从底至顶堆化 */
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let p = (i - 1) / self.arity;
+            if !self.higher(i, p) {
+                break;
+            }
+            self.swap(i, p);
+            i = p;
+        }
+    }
+
+    /* 从节点i开始，从顶至底堆化 */
+    fn sift_down(&mut self, mut i: usize) {
+        let len = self.entries.len();
+        loop {
+            // 在节点i及其至多d个子节点中找出优先级最高的节点
+            let first = self.arity * i + 1;
+            let mut top = i;
+            for child in first..(first + self.arity).min(len) {
+                if self.higher(child, top) {
+                    top = child;
+                }
+            }
+            if top == i {
+                break;
+            }
+            self.swap(i, top);
+            i = top;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P, C: Comparator<P> + Default> Default for IndexedHeap<K, P, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, P: fmt::Debug, C: Comparator<P>> fmt::Debug for IndexedHeap<K, P, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|e| (&e.key, &e.priority)))
+            .finish()
+    }
+}
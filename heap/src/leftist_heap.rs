@@ -0,0 +1,122 @@
+/* 左偏堆
+    左偏堆是一棵二叉树，每个节点都不大于它的子节点，并且满足“左偏”性质：
+    定义节点的秩(rank)为它到最近的空子树的距离，则任意节点左子节点的秩都不小于右子节点的秩。
+    于是最右路径最短，长度不超过log(n+1)。合并只沿着两棵树的最右路径进行：
+        1.取根较小的树，把它的右子树与另一棵树递归合并，结果作为新的右子树；
+        2.若右子节点的秩大于左子节点，交换左右子树；更新秩为右子节点的秩加1。
+    插入和删除最小值都归结为合并，最坏情况O(log n)。左偏堆没有父指针，不支持decrease_key。 */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::mergeable_heap::MergeableHeap;
+
+type NodeRef<T> = Rc<RefCell<LeftistNode<T>>>;
+
+/* 左偏堆节点 */
+struct LeftistNode<T> {
+    val: T,
+    rank: usize, // 到最近空子树的距离，叶节点为1
+    left: Option<NodeRef<T>>,
+    right: Option<NodeRef<T>>,
+}
+
+/* 左偏堆 */
+pub struct LeftistHeap<T> {
+    root: Option<NodeRef<T>>,
+    len: usize,
+}
+
+// 空子树的秩为0
+fn rank<T>(node: &Option<NodeRef<T>>) -> usize {
+    node.as_ref().map_or(0, |n| n.borrow().rank)
+}
+
+/* 沿最右路径合并两棵树，递归深度不超过两条最右路径的长度之和 */
+fn merge<T: Ord>(a: Option<NodeRef<T>>, b: Option<NodeRef<T>>) -> Option<NodeRef<T>> {
+    let (a, b) = match (a, b) {
+        (None, tree) | (tree, None) => return tree,
+        (Some(a), Some(b)) => (a, b),
+    };
+    let (small, large) = if b.borrow().val < a.borrow().val {
+        (b, a)
+    } else {
+        (a, b)
+    };
+    let right = small.borrow_mut().right.take();
+    let right = merge(right, Some(large));
+    {
+        let mut node = small.borrow_mut();
+        node.right = right;
+        // 保持左偏性质
+        if rank(&node.left) < rank(&node.right) {
+            let node = &mut *node;
+            std::mem::swap(&mut node.left, &mut node.right);
+        }
+        node.rank = rank(&node.right) + 1;
+    }
+    Some(small)
+}
+
+impl<T: Ord> MergeableHeap<T> for LeftistHeap<T> {
+    fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    fn size(&self) -> usize {
+        self.len
+    }
+
+    fn insert(&mut self, val: T) {
+        let node = Rc::new(RefCell::new(LeftistNode {
+            val,
+            rank: 1,
+            left: None,
+            right: None,
+        }));
+        self.root = merge(self.root.take(), Some(node));
+        self.len += 1;
+    }
+
+    fn peek_min(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.root.as_ref().map(|root| root.borrow().val.clone())
+    }
+
+    fn extract_min(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let node = Rc::try_unwrap(root)
+            .ok()
+            .expect("根节点不应被其他强引用持有")
+            .into_inner();
+        self.root = merge(node.left, node.right);
+        self.len -= 1;
+        Some(node.val)
+    }
+
+    fn meld(&mut self, mut other: Self) {
+        self.root = merge(self.root.take(), other.root.take());
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl<T: Ord> Default for LeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        // 左子树方向的路径可能很长，逐个拆开节点，避免递归析构导致栈溢出
+        let mut stack: Vec<NodeRef<T>> = self.root.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/* 堆的实现 */
+pub mod binary_heap;
+pub mod binomial_heap;
+pub mod indexed_heap;
+pub mod leftist_heap;
+pub mod mergeable_heap;
+pub mod pairing_heap;
@@ -0,0 +1,452 @@
+/* 堆是一种满足特定条件的完全二叉树，主要可分为两种类型：
+    1.小顶堆：任意节点的值 <= 其子节点的值。
+    2.大顶堆：任意节点的值 >= 其子节点的值。
+    堆的根节点称为“堆顶”，底层最靠右的节点称为“堆底”。大顶堆的堆顶是最大值，小顶堆的堆顶是最小值。
+    优先队列是一种抽象数据结构，定义为具有优先级排序的队列，通常使用堆来实现：入队即入堆，出队即弹出堆顶。 */
+
+use heap::binary_heap::{
+    k_way_merge, top_k, BinaryHeap, FnComparator, MaxComparator, MinComparator, PeekMut,
+};
+use heap::binomial_heap::BinomialHeap;
+use heap::indexed_heap::IndexedHeap;
+use heap::leftist_heap::LeftistHeap;
+use heap::mergeable_heap::{DecreaseKey, DecreaseKeyError, MergeableHeap};
+use heap::pairing_heap::PairingHeap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+
+fn main() {
+    /* 初始化堆 */
+    // 大顶堆
+    let mut max_heap: BinaryHeap<i32> = BinaryHeap::new();
+    // 小顶堆
+    let mut min_heap: BinaryHeap<i32, MinComparator> = BinaryHeap::new_min();
+
+    /* 元素入堆 */
+    for num in [1, 3, 2, 5, 4] {
+        max_heap.push(num);
+        min_heap.push(num);
+    }
+    println!("大顶堆(层序) = {:?}", max_heap);
+    println!("小顶堆(层序) = {:?}", min_heap);
+
+    /* 获取堆顶元素 */
+    println!("大顶堆堆顶 = {:?}, 小顶堆堆顶 = {:?}", max_heap.peek(), min_heap.peek());
+
+    /* 堆顶元素出堆 */
+    let pop = max_heap.pop();
+    println!("大顶堆出堆 {:?}，剩余 {:?}", pop, max_heap);
+
+    /* 获取堆大小 */
+    println!("堆大小 = {}，是否为空 = {}", max_heap.size(), max_heap.is_empty());
+
+    /* 输入列表并建堆 */
+    let heap = BinaryHeap::from_vec(vec![1, 3, 2, 5, 4], MinComparator);
+    println!("建堆后的小顶堆 = {:?}", heap);
+    println!("堆排序 = {:?}", heap.into_sorted_vec());
+
+    /* Top-k与多路归并 */
+    println!("最大的3个元素 = {:?}", top_k([7, 1, 9, 4, 8, 2], 3));
+    println!(
+        "多路归并 = {:?}",
+        k_way_merge(vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]])
+    );
+
+    /* 索引堆：可以修改任意键的优先级 */
+    let mut tasks: IndexedHeap<&str, u32> = IndexedHeap::with_arity(4);
+    tasks.push("编译", 5);
+    tasks.push("测试", 3);
+    tasks.push("部署", 8);
+    tasks.update_priority("部署", 1);
+    println!("索引堆堆顶 = {:?}", tasks.peek());
+
+    /* 可合并堆 */
+    let mut events: PairingHeap<u32> = PairingHeap::new();
+    let mut later: PairingHeap<u32> = PairingHeap::new();
+    events.insert(30);
+    later.insert(10);
+    later.insert(20);
+    events.meld(later);
+    println!("合并后的事件队列最小值 = {:?}，大小 = {}", events.peek_min(), events.size());
+
+    check_binary_heap();
+    check_peek_mut();
+    check_custom_comparator();
+    check_top_k();
+    check_k_way_merge();
+    check_indexed_heap();
+    check_dijkstra();
+    conformance::<PairingHeap<i64>>("PairingHeap");
+    conformance::<LeftistHeap<i64>>("LeftistHeap");
+    conformance::<BinomialHeap<i64>>("BinomialHeap");
+    decrease_key_conformance::<PairingHeap<i64>>("PairingHeap");
+    decrease_key_conformance::<BinomialHeap<i64>>("BinomialHeap");
+    println!("堆的检查全部通过");
+}
+
+/* 线性同余生成器(LCG)：state = state * a + c (mod 2^64)，a与c取自Knuth的MMIX。
+    低位的周期很短，因此只返回高31位。检查只需要可以重现的输入，不需要统计意义上的随机性。 */
+fn lcg(mut state: u64) -> impl FnMut() -> u64 {
+    move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        state >> 33
+    }
+}
+
+/* n个[0, modulo)内的伪随机数 */
+fn random_nums(seed: u64, n: usize, modulo: u64) -> Vec<i64> {
+    let mut next = lcg(seed);
+    (0..n).map(|_| (next() % modulo) as i64).collect()
+}
+
+/* 检查堆的性质：每个节点都不低于其子节点的优先级 */
+fn is_heap<T: Ord>(data: &[T], max: bool) -> bool {
+    (1..data.len()).all(|i| {
+        let p = (i - 1) / 2;
+        if max {
+            data[p] >= data[i]
+        } else {
+            data[p] <= data[i]
+        }
+    })
+}
+
+/* 与标准库的BinaryHeap对照，随机执行入堆、出堆与合并 */
+fn check_binary_heap() {
+    let ops = random_nums(1, 5000, 100);
+    let mut heap: BinaryHeap<i64> = BinaryHeap::new();
+    let mut expected = std::collections::BinaryHeap::new();
+    for (i, &op) in ops.iter().enumerate() {
+        if op < 60 {
+            heap.push(op * 7 % 97);
+            expected.push(op * 7 % 97);
+        } else {
+            assert_eq!(heap.pop(), expected.pop());
+        }
+        assert_eq!(heap.peek(), expected.peek());
+        assert_eq!(heap.size(), expected.len());
+        if i % 500 == 0 {
+            assert!(is_heap(&heap.clone().into_vec(), true));
+        }
+    }
+    // 空堆
+    let mut empty: BinaryHeap<i64> = BinaryHeap::new();
+    assert_eq!(empty.pop(), None);
+    assert!(empty.peek_mut().is_none());
+    assert_eq!(empty.into_sorted_vec(), Vec::<i64>::new());
+
+    // 建堆与堆排序
+    for n in [0, 1, 2, 3, 10, 257] {
+        let nums = random_nums(n as u64 + 7, n, 50);
+        let mut sorted = nums.clone();
+        sorted.sort();
+        let max_heap = BinaryHeap::from_vec(nums.clone(), MaxComparator);
+        assert!(is_heap(&max_heap.clone().into_vec(), true));
+        assert_eq!(max_heap.into_sorted_vec(), sorted);
+        let min_heap: BinaryHeap<i64, MinComparator> = nums.into_iter().collect();
+        assert!(is_heap(&min_heap.clone().into_vec(), false));
+        sorted.reverse();
+        assert_eq!(min_heap.into_sorted_vec(), sorted);
+    }
+
+    // 合并：两种路径(逐个入堆与重新建堆)都要覆盖
+    for (a, b) in [(100, 10), (10, 100), (0, 5), (5, 0)] {
+        let xs = random_nums(a as u64, a, 1000);
+        let ys = random_nums(b as u64 + 1000, b, 1000);
+        let mut left: BinaryHeap<i64> = xs.iter().copied().collect();
+        let right: BinaryHeap<i64> = ys.iter().copied().collect();
+        left.merge(right);
+        assert!(is_heap(&left.clone().into_vec(), true));
+        let mut all: Vec<i64> = xs.into_iter().chain(ys).collect();
+        all.sort();
+        assert_eq!(left.into_sorted_vec(), all);
+    }
+}
+
+/* peek_mut被丢弃时要重新堆化 */
+fn check_peek_mut() {
+    let mut heap: BinaryHeap<i32> = [5, 9, 3, 7, 1].into_iter().collect();
+    // 把堆顶改小，它应该下沉
+    if let Some(mut top) = heap.peek_mut() {
+        assert_eq!(*top, 9);
+        *top = 0;
+    }
+    assert_eq!(heap.peek(), Some(&7));
+    assert!(is_heap(&heap.clone().into_vec(), true));
+    // 不修改也没有问题
+    if let Some(top) = heap.peek_mut() {
+        assert_eq!(*top, 7);
+    }
+    assert_eq!(heap.peek(), Some(&7));
+    // 通过守卫直接弹出
+    let top = heap.peek_mut().map(PeekMut::pop);
+    assert_eq!(top, Some(7));
+    assert_eq!(heap.into_sorted_vec(), vec![0, 1, 3, 5]);
+}
+
+/* 自定义比较器：按字符串长度排序的大顶堆，以及用Reverse实现的小顶堆 */
+fn check_custom_comparator() {
+    let by_len = FnComparator(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+    let mut heap = BinaryHeap::with_comparator(by_len);
+    heap.extend(["ab", "abcd", "a", "abc"]);
+    let order: Vec<&str> = std::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(order, vec!["abcd", "abc", "ab", "a"]);
+
+    let mut tasks: BinaryHeap<(Reverse<u32>, &str)> = BinaryHeap::new();
+    tasks.push((Reverse(3), "低"));
+    tasks.push((Reverse(1), "高"));
+    tasks.push((Reverse(2), "中"));
+    let order: Vec<&str> = std::iter::from_fn(|| tasks.pop().map(|(_, name)| name)).collect();
+    assert_eq!(order, vec!["高", "中", "低"]);
+
+    // 比较器返回Equal时两者都可以在堆顶
+    let mut ties = BinaryHeap::with_comparator(FnComparator(|_: &i32, _: &i32| Ordering::Equal));
+    ties.extend([1, 2, 3]);
+    assert_eq!(ties.size(), 3);
+}
+
+fn check_top_k() {
+    let nums = random_nums(42, 1000, 10_000);
+    let mut sorted = nums.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+    for k in [0, 1, 5, 100, 1000, 2000] {
+        let expected: Vec<i64> = sorted.iter().copied().take(k).collect();
+        assert_eq!(top_k(nums.iter().copied(), k), expected);
+    }
+}
+
+fn check_k_way_merge() {
+    let mut sources = Vec::new();
+    let mut all = Vec::new();
+    for i in 0..8u64 {
+        let mut nums = random_nums(i, (i * 13 % 40) as usize, 100);
+        nums.sort();
+        all.extend(nums.iter().copied());
+        sources.push(nums);
+    }
+    sources.push(Vec::new());
+    all.sort();
+    assert_eq!(k_way_merge(sources), all);
+    assert_eq!(k_way_merge(Vec::<Vec<i32>>::new()), Vec::<i32>::new());
+}
+
+/* 与朴素的哈希表模型对照，随机执行入堆、出堆、修改与删除 */
+fn check_indexed_heap() {
+    for arity in [2, 3, 4, 8] {
+        let ops = random_nums(arity as u64, 20_000, 1000);
+        let mut heap: IndexedHeap<i64, i64> = IndexedHeap::with_arity(arity);
+        let mut model: HashMap<i64, i64> = HashMap::new();
+        for (i, op) in ops.iter().enumerate() {
+            let key = op % 64;
+            let priority = op * 31 % 1000;
+            match i % 5 {
+                0 | 1 => assert_eq!(heap.push(key, priority), model.insert(key, priority)),
+                2 => {
+                    let expected = model.get(&key).copied();
+                    assert_eq!(heap.update_priority(&key, priority), expected);
+                    if expected.is_some() {
+                        model.insert(key, priority);
+                    }
+                }
+                3 => assert_eq!(heap.remove(&key), model.remove(&key)),
+                _ => {
+                    // 优先级相同时出堆的键不确定，只比较优先级
+                    let expected = model.values().min().copied();
+                    let popped = heap.pop();
+                    assert_eq!(popped.map(|(_, p)| p), expected);
+                    if let Some((key, p)) = popped {
+                        assert_eq!(model.remove(&key), Some(p));
+                    }
+                }
+            }
+            assert_eq!(heap.size(), model.len());
+            assert_eq!(heap.contains(&key), model.contains_key(&key));
+            assert_eq!(heap.priority(&key), model.get(&key));
+        }
+        // decrease_key只接受更优的优先级
+        heap.clear();
+        heap.push(1, 10);
+        assert!(!heap.decrease_key(&1, 20));
+        assert!(heap.decrease_key(&1, 5));
+        assert!(!heap.decrease_key(&2, 0));
+        assert_eq!(heap.pop(), Some((1, 5)));
+        assert!(heap.is_empty());
+    }
+}
+
+/* 用索引堆实现Dijkstra，并与“允许重复入堆”的BinaryHeap版本对照 */
+fn check_dijkstra() {
+    let n = 300;
+    let edges = random_nums(2024, n * 6, 1_000_000);
+    let mut graph: Vec<Vec<(usize, u64)>> = vec![Vec::new(); n];
+    for chunk in edges.chunks(2) {
+        let (u, v) = (chunk[0] as usize % n, chunk[1] as usize % n);
+        let w = (chunk[0] + chunk[1]) as u64 % 100 + 1;
+        graph[u].push((v, w));
+        graph[v].push((u, w));
+    }
+    for arity in [2, 4, 8] {
+        let mut dist = vec![u64::MAX; n];
+        let mut heap: IndexedHeap<usize, u64> = IndexedHeap::with_arity(arity);
+        dist[0] = 0;
+        heap.push(0, 0);
+        while let Some((u, d)) = heap.pop() {
+            for &(v, w) in &graph[u] {
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    if !heap.decrease_key(&v, d + w) {
+                        heap.push(v, d + w);
+                    }
+                }
+            }
+        }
+
+        let mut expected = vec![u64::MAX; n];
+        let mut lazy: BinaryHeap<(u64, usize), MinComparator> = BinaryHeap::new_min();
+        expected[0] = 0;
+        lazy.push((0, 0));
+        while let Some((d, u)) = lazy.pop() {
+            if d > expected[u] {
+                continue;
+            }
+            for &(v, w) in &graph[u] {
+                if d + w < expected[v] {
+                    expected[v] = d + w;
+                    lazy.push((d + w, v));
+                }
+            }
+        }
+        assert_eq!(dist, expected);
+    }
+}
+
+/* 可合并堆的通用检查：随机插入、删除最小值与合并，结果与有序多重集合对照 */
+fn conformance<H: MergeableHeap<i64>>(name: &str) {
+    fn model_pop(model: &mut BTreeMap<i64, usize>) -> Option<i64> {
+        let (&min, count) = model.iter_mut().next()?;
+        *count -= 1;
+        if *count == 0 {
+            model.remove(&min);
+        }
+        Some(min)
+    }
+
+    let ops = random_nums(name.len() as u64, 20_000, 1000);
+    let mut heap = H::new();
+    let mut model: BTreeMap<i64, usize> = BTreeMap::new();
+    let mut count = 0;
+    for (i, &op) in ops.iter().enumerate() {
+        match op % 10 {
+            0..=5 => {
+                heap.insert(op % 300);
+                *model.entry(op % 300).or_default() += 1;
+                count += 1;
+            }
+            6..=8 => {
+                let expected = model_pop(&mut model);
+                count -= expected.is_some() as usize;
+                assert_eq!(heap.extract_min(), expected, "{}", name);
+            }
+            _ => {
+                // 合并一个随机大小的堆
+                let mut other = H::new();
+                for v in random_nums(i as u64, (op % 20) as usize, 300) {
+                    other.insert(v);
+                    *model.entry(v).or_default() += 1;
+                    count += 1;
+                }
+                heap.meld(other);
+            }
+        }
+        assert_eq!(heap.size(), count, "{}", name);
+        assert_eq!(heap.peek_min(), model.keys().next().copied(), "{}", name);
+    }
+    while let Some(min) = heap.extract_min() {
+        assert_eq!(Some(min), model_pop(&mut model), "{}", name);
+    }
+    assert!(heap.is_empty() && model.is_empty(), "{}", name);
+
+    // 与空堆合并
+    let mut empty = H::new();
+    empty.meld(H::new());
+    assert_eq!(empty.extract_min(), None);
+
+    // 单调插入会形成很长的链，析构时不能栈溢出
+    let mut deep = H::new();
+    for v in (0..100_000).rev() {
+        deep.insert(v);
+    }
+    let mut ascending = H::new();
+    for v in 0..100_000 {
+        ascending.insert(v);
+    }
+    deep.meld(ascending);
+    assert_eq!(deep.extract_min(), Some(0));
+    drop(deep);
+}
+
+/* decrease_key的检查：值编码为 优先级 * 10000 + 编号，保证互不相同，出堆时可以据此找到对应的句柄 */
+fn decrease_key_conformance<H: DecreaseKey<i64>>(name: &str) {
+    const IDS: i64 = 10_000;
+    let ops = random_nums(name.len() as u64 + 1, 20_000, 1_000_000);
+    let mut heap = H::new();
+    let mut handles: Vec<H::Handle> = Vec::new();
+    let mut current: Vec<Option<i64>> = Vec::new(); // 每个编号当前的值，出堆后为None
+    let mut model: BTreeMap<i64, ()> = BTreeMap::new();
+    for &op in &ops {
+        let id = handles.len() as i64;
+        match op % 4 {
+            0 if id < IDS => {
+                let val = (op % 1000 + 1000) * IDS + id;
+                handles.push(heap.insert_with_handle(val));
+                current.push(Some(val));
+                model.insert(val, ());
+            }
+            1 => {
+                let popped = heap.extract_min();
+                assert_eq!(popped, model.pop_first().map(|(v, _)| v), "{}", name);
+                if let Some(val) = popped {
+                    current[val.rem_euclid(IDS) as usize] = None;
+                }
+            }
+            _ if !handles.is_empty() => {
+                let target = (op as usize / 4) % handles.len();
+                let handle = &handles[target];
+                match current[target] {
+                    Some(old) => {
+                        let val = old - (op % 1000) * IDS;
+                        assert_eq!(heap.decrease_key(handle, val), Ok(()), "{}", name);
+                        model.remove(&old);
+                        model.insert(val, ());
+                        current[target] = Some(val);
+                        // 值变大应当被拒绝
+                        assert_eq!(
+                            heap.decrease_key(handle, val + IDS),
+                            Err(DecreaseKeyError::KeyIncreased),
+                            "{}",
+                            name
+                        );
+                    }
+                    None => assert_eq!(
+                        heap.decrease_key(handle, 0),
+                        Err(DecreaseKeyError::Removed),
+                        "{}",
+                        name
+                    ),
+                }
+            }
+            _ => {}
+        }
+        assert_eq!(heap.peek_min(), model.keys().next().copied(), "{}", name);
+    }
+
+    // 合并后，另一个堆的句柄依然有效
+    let mut other = H::new();
+    let handle = other.insert_with_handle(i64::MAX);
+    heap.meld(other);
+    assert_eq!(heap.decrease_key(&handle, i64::MIN), Ok(()));
+    assert_eq!(heap.extract_min(), Some(i64::MIN));
+    assert_eq!(heap.decrease_key(&handle, i64::MIN), Err(DecreaseKeyError::Removed));
+}
@@ -0,0 +1,66 @@
+/* 可合并堆
+    二叉堆合并两个堆需要O(n)时间。事件驱动的模拟中经常要把一整个事件队列并入另一个，
+    因此需要专门支持快速合并(meld)的堆：它们都由节点和引用组成，合并只需要重新连接少量节点。
+        1.配对堆：插入与合并O(1)，删除最小值均摊O(log n)，decrease_key均摊o(log n)，实践中非常快。
+        2.左偏堆：最坏情况下合并、插入、删除最小值都是O(log n)，不支持decrease_key。
+        3.二项堆：由若干棵二项树组成，像二进制加法一样合并，所有操作最坏O(log n)，支持decrease_key。 */
+
+use std::fmt;
+
+/* 可合并的小顶堆 */
+pub trait MergeableHeap<T: Ord>: Sized {
+    // 创建空堆
+    fn new() -> Self;
+
+    // 获取堆的大小
+    fn size(&self) -> usize;
+
+    // 判断堆是否为空
+    fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    // 插入元素
+    fn insert(&mut self, val: T);
+
+    // 获取最小值的拷贝；节点保存在RefCell中，无法直接返回引用
+    fn peek_min(&self) -> Option<T>
+    where
+        T: Clone;
+
+    // 删除并返回最小值
+    fn extract_min(&mut self) -> Option<T>;
+
+    // 把另一个堆合并进来
+    fn meld(&mut self, other: Self);
+}
+
+/* 支持decrease_key的可合并堆：插入时返回句柄，之后可以通过句柄降低元素的值 */
+pub trait DecreaseKey<T: Ord>: MergeableHeap<T> {
+    // 句柄只保存弱引用，元素出堆后句柄失效
+    type Handle: Clone;
+
+    // 插入元素并返回句柄
+    fn insert_with_handle(&mut self, val: T) -> Self::Handle;
+
+    // 把句柄对应元素的值降低为val；句柄必须来自当前堆或已经并入当前堆的堆
+    fn decrease_key(&mut self, handle: &Self::Handle, val: T) -> Result<(), DecreaseKeyError>;
+}
+
+/* decrease_key失败的原因 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecreaseKeyError {
+    Removed,      // 元素已经出堆
+    KeyIncreased, // 新值大于旧值
+}
+
+impl fmt::Display for DecreaseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecreaseKeyError::Removed => write!(f, "元素已经出堆"),
+            DecreaseKeyError::KeyIncreased => write!(f, "新值大于旧值"),
+        }
+    }
+}
+
+impl std::error::Error for DecreaseKeyError {}
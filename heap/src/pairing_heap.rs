@@ -0,0 +1,204 @@
+/* 配对堆
+    配对堆是一棵多叉树，每个节点都不大于它的子节点。子节点用“左孩子右兄弟”的方式存储：
+    节点只保存第一个子节点，子节点之间通过sibling串成链表。
+        1.合并：比较两个根节点，较大者成为较小者的第一个子节点，O(1)。
+        2.插入：与只有一个节点的堆合并，O(1)。
+        3.删除最小值：删除根节点后，先从左到右把子节点两两合并，再从右到左依次合并成一棵树(两趟配对)，均摊O(log n)。
+        4.decrease_key：把节点连同其子树从原位置剪下，降低值后与根合并。
+    为了在O(1)时间内剪下节点，每个节点还保存prev：若它是第一个子节点则指向父节点，否则指向左边的兄弟。 */
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use crate::mergeable_heap::{DecreaseKey, DecreaseKeyError, MergeableHeap};
+
+type NodeRef<T> = Rc<RefCell<PairingNode<T>>>;
+
+/* 配对堆节点 */
+struct PairingNode<T> {
+    val: T,
+    child: Option<NodeRef<T>>,                  // 第一个子节点
+    sibling: Option<NodeRef<T>>,                // 右边的兄弟
+    prev: Option<Weak<RefCell<PairingNode<T>>>>, // 父节点或左边的兄弟
+}
+
+/* 配对堆 */
+pub struct PairingHeap<T> {
+    root: Option<NodeRef<T>>,
+    len: usize,
+}
+
+/* 配对堆的元素句柄 */
+pub struct PairingHandle<T>(Weak<RefCell<PairingNode<T>>>);
+
+impl<T> Clone for PairingHandle<T> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+/* 合并两棵树：较大的根成为较小的根的第一个子节点 */
+fn link<T: Ord>(a: NodeRef<T>, b: NodeRef<T>) -> NodeRef<T> {
+    let (parent, child) = if b.borrow().val < a.borrow().val {
+        (b, a)
+    } else {
+        (a, b)
+    };
+    {
+        let mut p = parent.borrow_mut();
+        let mut c = child.borrow_mut();
+        if let Some(first) = p.child.take() {
+            first.borrow_mut().prev = Some(Rc::downgrade(&child));
+            c.sibling = Some(first);
+        }
+        c.prev = Some(Rc::downgrade(&parent));
+    }
+    parent.borrow_mut().child = Some(child);
+    parent
+}
+
+/* 两趟配对：第一趟从左到右两两合并，第二趟从右到左合并成一棵树 */
+fn merge_pairs<T: Ord>(first: Option<NodeRef<T>>) -> Option<NodeRef<T>> {
+    let mut pairs = Vec::new();
+    let mut cur = first;
+    while let Some(a) = cur {
+        let b = {
+            let mut a = a.borrow_mut();
+            a.prev = None;
+            a.sibling.take()
+        };
+        match b {
+            Some(b) => {
+                cur = {
+                    let mut b = b.borrow_mut();
+                    b.prev = None;
+                    b.sibling.take()
+                };
+                pairs.push(link(a, b));
+            }
+            None => {
+                cur = None;
+                pairs.push(a);
+            }
+        }
+    }
+    let mut root = pairs.pop()?;
+    while let Some(tree) = pairs.pop() {
+        root = link(tree, root);
+    }
+    Some(root)
+}
+
+impl<T: Ord> PairingHeap<T> {
+    // 把一棵独立的树并入堆
+    fn meld_tree(&mut self, tree: NodeRef<T>) {
+        self.root = Some(match self.root.take() {
+            Some(root) => link(root, tree),
+            None => tree,
+        });
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for PairingHeap<T> {
+    fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    fn size(&self) -> usize {
+        self.len
+    }
+
+    fn insert(&mut self, val: T) {
+        self.insert_with_handle(val);
+    }
+
+    fn peek_min(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.root.as_ref().map(|root| root.borrow().val.clone())
+    }
+
+    fn extract_min(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        // 堆是节点唯一的强引用持有者，句柄和prev都是弱引用
+        let node = Rc::try_unwrap(root)
+            .ok()
+            .expect("根节点不应被其他强引用持有")
+            .into_inner();
+        self.root = merge_pairs(node.child);
+        self.len -= 1;
+        Some(node.val)
+    }
+
+    fn meld(&mut self, mut other: Self) {
+        if let Some(tree) = other.root.take() {
+            self.meld_tree(tree);
+        }
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl<T: Ord> DecreaseKey<T> for PairingHeap<T> {
+    type Handle = PairingHandle<T>;
+
+    fn insert_with_handle(&mut self, val: T) -> PairingHandle<T> {
+        let node = Rc::new(RefCell::new(PairingNode {
+            val,
+            child: None,
+            sibling: None,
+            prev: None,
+        }));
+        let handle = PairingHandle(Rc::downgrade(&node));
+        self.meld_tree(node);
+        self.len += 1;
+        handle
+    }
+
+    fn decrease_key(&mut self, handle: &PairingHandle<T>, val: T) -> Result<(), DecreaseKeyError> {
+        let node = handle.0.upgrade().ok_or(DecreaseKeyError::Removed)?;
+        if val > node.borrow().val {
+            return Err(DecreaseKeyError::KeyIncreased);
+        }
+        node.borrow_mut().val = val;
+        // 没有prev说明它就是根节点，值变小不影响堆的性质
+        let Some(prev) = node.borrow_mut().prev.take() else {
+            return Ok(());
+        };
+        let prev = prev.upgrade().expect("非根节点的prev一定存在");
+        // 把节点连同子树从兄弟链表中剪下
+        let sibling = node.borrow_mut().sibling.take();
+        if let Some(sibling) = &sibling {
+            sibling.borrow_mut().prev = Some(Rc::downgrade(&prev));
+        }
+        {
+            let mut p = prev.borrow_mut();
+            if p.child.as_ref().is_some_and(|c| Rc::ptr_eq(c, &node)) {
+                p.child = sibling;
+            } else {
+                p.sibling = sibling;
+            }
+        }
+        self.meld_tree(node);
+        Ok(())
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        // 兄弟链表可能很长，逐个拆开节点，避免递归析构导致栈溢出
+        let mut stack: Vec<NodeRef<T>> = self.root.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.sibling.take());
+        }
+    }
+}
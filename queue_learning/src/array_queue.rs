@@ -0,0 +1,249 @@
+/* 基于数组的实现 */
+// 使用环形数组，让front或rear在越过数组尾部时，直接回到数组头部继续遍历。这种周期性规律可以通过“取余操作”来实现
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::slice;
+
+/* 计算环形数组下标：从front向后偏移offset个位置，越过数组尾部则回到头部 */
+#[inline]
+pub(crate) fn wrap_index(front: usize, offset: usize, capacity: usize) -> usize {
+    (front + offset) % capacity
+}
+
+/* 队列已满时的处理策略 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    Reject,    // 拒绝入队，将元素通过Err原样返回
+    Overwrite, // 覆盖最旧的元素(音频环形缓冲区模式)
+    Grow,      // 扩容为原来的2倍，并将元素重新排列到数组头部
+}
+
+/* 队列已满且策略为Reject时返回的错误，携带未能入队的元素 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueFull<T>(pub T);
+
+impl<T> fmt::Display for QueueFull<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "队列已满")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for QueueFull<T> {}
+
+// 基于环形数组实现的队列
+pub struct ArrayQueue<T> {
+    elems: Vec<MaybeUninit<T>>, // 用于存储队列元素的数组，长度即为队列容量
+    front: usize,               // 队首指针，指向队首元素
+    que_size: usize,            // 队列长度
+    policy: OverflowPolicy,     // 队列已满时的处理策略
+}
+
+impl<T> ArrayQueue<T> {
+    // 构造函数，队列已满时拒绝入队
+    pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, OverflowPolicy::Reject)
+    }
+
+    // 指定溢出策略的构造函数
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            elems: Self::alloc(capacity),
+            front: 0,
+            que_size: 0,
+            policy,
+        }
+    }
+
+    // 分配一段未初始化的数组
+    fn alloc(capacity: usize) -> Vec<MaybeUninit<T>> {
+        (0..capacity).map(|_| MaybeUninit::uninit()).collect()
+    }
+
+    // 获取队列长度
+    pub fn size(&self) -> usize {
+        self.que_size
+    }
+
+    // 获取队列容量
+    pub fn capacity(&self) -> usize {
+        self.elems.len()
+    }
+
+    // 获取溢出策略
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    // 判断队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.que_size == 0
+    }
+
+    // 判断队列是否已满
+    pub fn is_full(&self) -> bool {
+        self.que_size == self.capacity()
+    }
+
+    /* 入队
+        返回Ok(None)表示正常入队；Overwrite策略下返回Ok(Some(被覆盖的最旧元素))；
+        Reject策略下队列已满则返回Err(QueueFull(elem))。 */
+    pub fn push(&mut self, elem: T) -> Result<Option<T>, QueueFull<T>> {
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Reject => return Err(QueueFull(elem)),
+                OverflowPolicy::Overwrite => {
+                    // 容量为0时无处可写，新元素即为被“覆盖”的元素
+                    if self.capacity() == 0 {
+                        return Ok(Some(elem));
+                    }
+                    // 先弹出最旧的元素，再写入队尾
+                    let oldest = self.pop();
+                    self.push_unchecked(elem);
+                    return Ok(oldest);
+                }
+                OverflowPolicy::Grow => self.grow(),
+            }
+        }
+        self.push_unchecked(elem);
+        Ok(None)
+    }
+
+    // 在确定队列未满时入队
+    fn push_unchecked(&mut self, elem: T) {
+        // 计算队尾指针，指向队尾索引+1
+        // 通过取余操作实现rear越过数组尾部后回到头部
+        let rear = wrap_index(self.front, self.que_size, self.capacity());
+        // 将elem添加到队尾
+        self.elems[rear].write(elem);
+        self.que_size += 1;
+    }
+
+    // 扩容：新数组容量为原来的2倍，元素按先进先出的顺序重新排列到新数组头部
+    fn grow(&mut self) {
+        let new_capacity = (self.capacity() * 2).max(1);
+        let mut elems = Self::alloc(new_capacity);
+        for (i, slot) in elems.iter_mut().take(self.que_size).enumerate() {
+            let j = wrap_index(self.front, i, self.capacity());
+            // SAFETY: 偏移量小于que_size的位置都已初始化，且每个元素只会被搬运一次
+            slot.write(unsafe { self.elems[j].assume_init_read() });
+        }
+        self.elems = elems;
+        self.front = 0;
+    }
+
+    // 出队
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        // SAFETY: 队列非空时队首位置已初始化，读出后该位置即视为空闲
+        let elem = unsafe { self.elems[self.front].assume_init_read() };
+        // 将队首的指针向后移动一位，如果越过尾部，则返回数组头部
+        self.front = wrap_index(self.front, 1, self.capacity());
+        self.que_size -= 1;
+        Some(elem)
+    }
+
+    // 访问队首元素
+    pub fn peek(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    // 访问并修改队首元素
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None;
+        }
+        // SAFETY: 队列非空时队首位置已初始化
+        Some(unsafe { self.elems[self.front].assume_init_mut() })
+    }
+
+    /* 以两个切片的形式返回队列元素：第一个切片从队首到数组尾部，第二个切片从数组头部到队尾 */
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let cap = self.capacity();
+        let head_len = self.que_size.min(cap - self.front);
+        let tail_len = self.que_size - head_len;
+        let ptr = self.elems.as_ptr() as *const T;
+        // SAFETY: 两段区间内的元素都已初始化，MaybeUninit<T>与T的内存布局相同
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(self.front), head_len),
+                slice::from_raw_parts(ptr, tail_len),
+            )
+        }
+    }
+
+    // 按先进先出的顺序遍历
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        head.iter().chain(tail.iter())
+    }
+
+    // 依次取出队列中的全部元素，迭代器被丢弃时未取出的元素也会被移除
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { queue: self }
+    }
+
+    // 清空队列
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    // 返回数组
+    pub fn to_array(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Drop for ArrayQueue<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArrayQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/* 先进先出顺序的迭代器 */
+pub type Iter<'a, T> = std::iter::Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+impl<'a, T> IntoIterator for &'a ArrayQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/* drain返回的迭代器 */
+pub struct Drain<'a, T> {
+    queue: &'a mut ArrayQueue<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.size(), Some(self.queue.size()))
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.queue.clear();
+    }
+}
@@ -0,0 +1,296 @@
+/* 异步有界通道
+    与BlockingQueue的思路相同，只是“等待”不再阻塞线程：队列已满时send()返回的Future登记自己的Waker并返回Pending，
+    接收者取走一个元素后唤醒一个等待中的发送者；队列为空时recv()同样登记Waker，发送者入队后唤醒它。
+        1.多个发送者(Sender可以克隆)，一个接收者。
+        2.所有Sender被丢弃或接收者调用close()后通道关闭：send返回Err，recv取完剩余元素后返回None。
+        3.取消安全：send()的Future在完成前被丢弃，元素不会被发送，并且它占用的唤醒机会会转交给下一个等待者；
+          recv()的Future被丢弃不会丢失任何元素。 */
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+use crate::array_queue::ArrayQueue;
+
+/* 通道关闭时send返回的错误，携带未能发送的元素 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "通道已关闭")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+
+/* try_send失败的原因 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    Full(T),   // 通道已满
+    Closed(T), // 通道已关闭
+}
+
+/* try_recv失败的原因 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,  // 通道为空
+    Closed, // 通道已关闭且已取空
+}
+
+/* 受互斥锁保护的通道状态 */
+struct State<T> {
+    queue: ArrayQueue<T>,
+    closed: bool,
+    senders: usize,                   // 存活的Sender数量
+    recv_waker: Option<Waker>,        // 等待元素的接收者
+    send_waiters: Vec<(u64, Waker)>,  // 等待空位的发送者，按登记顺序排列
+    next_id: u64,                     // 分配给send Future的编号
+}
+
+impl<T> State<T> {
+    // 唤醒最早登记的一个发送者
+    fn wake_sender(&mut self) {
+        if !self.send_waiters.is_empty() {
+            let (_, waker) = self.send_waiters.remove(0);
+            waker.wake();
+        }
+    }
+
+    // 唤醒所有等待者
+    fn wake_all(&mut self) {
+        if let Some(waker) = self.recv_waker.take() {
+            waker.wake();
+        }
+        for (_, waker) in self.send_waiters.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+struct Chan<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> Chan<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/* 创建容量为capacity的异步通道 */
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "通道容量必须大于0");
+    let chan = Arc::new(Chan {
+        state: Mutex::new(State {
+            queue: ArrayQueue::new(capacity),
+            closed: false,
+            senders: 1,
+            recv_waker: None,
+            send_waiters: Vec::new(),
+            next_id: 0,
+        }),
+    });
+    (
+        Sender {
+            chan: Arc::clone(&chan),
+        },
+        Receiver { chan },
+    )
+}
+
+/* 发送端 */
+pub struct Sender<T> {
+    chan: Arc<Chan<T>>,
+}
+
+impl<T> Sender<T> {
+    /* 异步发送，通道已满时等待空位 */
+    pub fn send(&self, elem: T) -> SendFuture<'_, T> {
+        SendFuture {
+            chan: &self.chan,
+            elem: Some(elem),
+            id: None,
+        }
+    }
+
+    /* 非阻塞发送 */
+    pub fn try_send(&self, elem: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.chan.lock();
+        if state.closed {
+            return Err(TrySendError::Closed(elem));
+        }
+        if state.queue.is_full() {
+            return Err(TrySendError::Full(elem));
+        }
+        let _ = state.queue.push(elem);
+        if let Some(waker) = state.recv_waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    // 判断通道是否已关闭
+    pub fn is_closed(&self) -> bool {
+        self.chan.lock().closed
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.lock().senders += 1;
+        Self {
+            chan: Arc::clone(&self.chan),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.chan.lock();
+        state.senders -= 1;
+        // 最后一个发送者离开，通知接收者不会再有新元素
+        if state.senders == 0 {
+            state.closed = true;
+            state.wake_all();
+        }
+    }
+}
+
+/* send()返回的Future */
+pub struct SendFuture<'a, T> {
+    chan: &'a Chan<T>,
+    elem: Option<T>,
+    id: Option<u64>, // 登记为等待者后分配的编号
+}
+
+// SendFuture不包含自引用，可以安全地移动
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.chan.lock();
+        let elem = this.elem.take().expect("SendFuture在完成后不能再次poll");
+        if state.closed {
+            this.unregister(&mut state);
+            return Poll::Ready(Err(SendError(elem)));
+        }
+        if !state.queue.is_full() {
+            this.unregister(&mut state);
+            let _ = state.queue.push(elem);
+            if let Some(waker) = state.recv_waker.take() {
+                waker.wake();
+            }
+            return Poll::Ready(Ok(()));
+        }
+        // 通道已满，登记(或更新)自己的Waker
+        this.elem = Some(elem);
+        let id = *this.id.get_or_insert_with(|| {
+            state.next_id += 1;
+            state.next_id
+        });
+        match state.send_waiters.iter_mut().find(|(i, _)| *i == id) {
+            Some((_, waker)) => waker.clone_from(cx.waker()),
+            None => state.send_waiters.push((id, cx.waker().clone())),
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> SendFuture<'_, T> {
+    // 从等待列表中移除自己
+    fn unregister(&mut self, state: &mut State<T>) {
+        if let Some(id) = self.id.take() {
+            state.send_waiters.retain(|(i, _)| *i != id);
+        }
+    }
+}
+
+impl<T> Drop for SendFuture<'_, T> {
+    fn drop(&mut self) {
+        // 未完成就被取消：元素随Future一起丢弃，不会被发送
+        if self.elem.is_none() {
+            return;
+        }
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.chan.lock();
+        let was_waiting = state.send_waiters.iter().any(|(i, _)| *i == id);
+        state.send_waiters.retain(|(i, _)| *i != id);
+        // 已被唤醒却没来得及使用空位，把唤醒机会转交给下一个发送者
+        if !was_waiting && !state.queue.is_full() {
+            state.wake_sender();
+        }
+    }
+}
+
+/* 接收端 */
+pub struct Receiver<T> {
+    chan: Arc<Chan<T>>,
+}
+
+impl<T> Receiver<T> {
+    /* 异步接收，通道为空时等待；通道关闭且已取空时返回None */
+    pub fn recv(&mut self) -> RecvFuture<'_, T> {
+        RecvFuture { chan: &self.chan }
+    }
+
+    /* 非阻塞接收 */
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.chan.lock();
+        match state.queue.pop() {
+            Some(elem) => {
+                state.wake_sender();
+                Ok(elem)
+            }
+            None if state.closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /* 关闭通道：之后的发送都会失败，已经在通道中的元素仍然可以取出 */
+    pub fn close(&mut self) {
+        let mut state = self.chan.lock();
+        state.closed = true;
+        state.wake_all();
+    }
+
+    // 通道中的元素数量
+    pub fn size(&self) -> usize {
+        self.chan.lock().queue.size()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/* recv()返回的Future */
+pub struct RecvFuture<'a, T> {
+    chan: &'a Chan<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.chan.lock();
+        if let Some(elem) = state.queue.pop() {
+            state.wake_sender();
+            return Poll::Ready(Some(elem));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.recv_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
@@ -0,0 +1,247 @@
+/* 有界阻塞队列
+    打印机任务队列、订单处理队列通常由多个线程同时入队和出队。阻塞队列在环形数组队列外面包一层Mutex，
+    并用两个Condvar分别等待“队列不满”和“队列非空”：队列已满时入队线程睡眠，队列为空时出队线程睡眠。
+    close()之后不再接受新元素，所有等待中的线程都会被唤醒，出队线程取完剩余元素后得到关闭信号。 */
+
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::array_queue::ArrayQueue;
+
+/* 入队失败的原因，携带未能入队的元素 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushError<T> {
+    Full(T),    // 队列已满(仅try_push)
+    Timeout(T), // 等待超时(仅push_timeout)
+    Closed(T),  // 队列已关闭
+}
+
+impl<T> PushError<T> {
+    // 取回未能入队的元素
+    pub fn into_inner(self) -> T {
+        match self {
+            PushError::Full(elem) | PushError::Timeout(elem) | PushError::Closed(elem) => elem,
+        }
+    }
+}
+
+impl<T> fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushError::Full(_) => write!(f, "队列已满"),
+            PushError::Timeout(_) => write!(f, "入队超时"),
+            PushError::Closed(_) => write!(f, "队列已关闭"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for PushError<T> {}
+
+/* 出队失败的原因 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopError {
+    Empty,   // 队列为空(仅try_pop)
+    Timeout, // 等待超时(仅pop_timeout)
+    Closed,  // 队列已关闭且已取空
+}
+
+impl fmt::Display for PopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PopError::Empty => write!(f, "队列为空"),
+            PopError::Timeout => write!(f, "出队超时"),
+            PopError::Closed => write!(f, "队列已关闭"),
+        }
+    }
+}
+
+impl std::error::Error for PopError {}
+
+/* 受互斥锁保护的状态 */
+struct State<T> {
+    queue: ArrayQueue<T>,
+    closed: bool,
+}
+
+/* 有界阻塞队列，多个生产者和多个消费者可以通过Arc共享同一个队列 */
+pub struct BlockingQueue<T> {
+    state: Mutex<State<T>>,
+    not_full: Condvar,  // 队列不满时通知入队线程
+    not_empty: Condvar, // 队列非空或关闭时通知出队线程
+}
+
+impl<T> BlockingQueue<T> {
+    // 构造函数，capacity必须大于0
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "阻塞队列的容量必须大于0");
+        Self {
+            state: Mutex::new(State {
+                queue: ArrayQueue::new(capacity),
+                closed: false,
+            }),
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }
+    }
+
+    // 加锁；持锁线程panic时锁会中毒，但队列本身的状态仍然一致，因此直接取回
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // 获取队列长度
+    pub fn size(&self) -> usize {
+        self.lock().queue.size()
+    }
+
+    // 判断队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.lock().queue.is_empty()
+    }
+
+    // 获取队列容量
+    pub fn capacity(&self) -> usize {
+        self.lock().queue.capacity()
+    }
+
+    // 判断队列是否已关闭
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    // 在持锁且确认队列未满时入队，并唤醒一个出队线程
+    fn push_locked(&self, state: &mut State<T>, elem: T) {
+        if state.queue.push(elem).is_err() {
+            unreachable!("调用方已确认队列未满");
+        }
+        self.not_empty.notify_one();
+    }
+
+    // 在持锁且确认队列非空时出队，并唤醒一个入队线程
+    fn pop_locked(&self, state: &mut State<T>) -> T {
+        let elem = state.queue.pop().expect("调用方已确认队列非空");
+        self.not_full.notify_one();
+        elem
+    }
+
+    /* 阻塞入队：队列已满时等待，队列关闭时返回Err(PushError::Closed) */
+    pub fn push(&self, elem: T) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        loop {
+            if state.closed {
+                return Err(PushError::Closed(elem));
+            }
+            if !state.queue.is_full() {
+                self.push_locked(&mut state, elem);
+                return Ok(());
+            }
+            state = self.not_full.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /* 阻塞出队：队列为空时等待，队列关闭且已取空时返回None */
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self.lock();
+        loop {
+            if !state.queue.is_empty() {
+                return Some(self.pop_locked(&mut state));
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.not_empty.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /* 非阻塞入队 */
+    pub fn try_push(&self, elem: T) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(PushError::Closed(elem));
+        }
+        if state.queue.is_full() {
+            return Err(PushError::Full(elem));
+        }
+        self.push_locked(&mut state, elem);
+        Ok(())
+    }
+
+    /* 非阻塞出队 */
+    pub fn try_pop(&self) -> Result<T, PopError> {
+        let mut state = self.lock();
+        if !state.queue.is_empty() {
+            return Ok(self.pop_locked(&mut state));
+        }
+        if state.closed {
+            return Err(PopError::Closed);
+        }
+        Err(PopError::Empty)
+    }
+
+    /* 带超时的入队，最多等待timeout */
+    pub fn push_timeout(&self, elem: T, timeout: Duration) -> Result<(), PushError<T>> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            if state.closed {
+                return Err(PushError::Closed(elem));
+            }
+            if !state.queue.is_full() {
+                self.push_locked(&mut state, elem);
+                return Ok(());
+            }
+            // 被虚假唤醒时按剩余时间继续等待
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(PushError::Timeout(elem));
+            }
+            state = self
+                .not_full
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /* 带超时的出队，最多等待timeout */
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<T, PopError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            if !state.queue.is_empty() {
+                return Ok(self.pop_locked(&mut state));
+            }
+            if state.closed {
+                return Err(PopError::Closed);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(PopError::Timeout);
+            }
+            state = self
+                .not_empty
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /* 关闭队列：拒绝后续入队，并唤醒所有等待中的线程；已入队的元素仍可被取出 */
+    pub fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        drop(state);
+        self.not_full.notify_all();
+        self.not_empty.notify_all();
+    }
+
+    /* 非阻塞地取出当前所有元素 */
+    pub fn drain(&self) -> Vec<T> {
+        let mut state = self.lock();
+        let elems: Vec<T> = state.queue.drain().collect();
+        drop(state);
+        self.not_full.notify_all();
+        elems
+    }
+}
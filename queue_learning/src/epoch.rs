@@ -0,0 +1,176 @@
+/* 基于纪元的内存回收(epoch-based reclamation)
+    无锁数据结构中，一个线程把节点从链表上摘下之后，其他线程可能仍然持有指向该节点的指针，因此不能立即释放。
+    纪元回收维护一个全局纪元号：
+        1.线程访问共享节点之前先pin()，把当前全局纪元记录到自己的槽位中，访问结束后(Guard被丢弃)清空槽位。
+        2.被摘下的节点不立即释放，而是连同当时的全局纪元一起放入垃圾列表。
+        3.只有当所有处于pin状态的线程都已经观察到当前全局纪元时，全局纪元才能加1。
+        4.在纪元e被丢弃的垃圾，等到全局纪元不小于e+2时，所有可能看到它的线程都已经离开，可以安全释放。 */
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::spsc::CachePadded;
+
+// 同时处于pin状态的线程数上限，超过时后来的线程会等待空闲槽位
+const SLOTS: usize = 64;
+// 每积累这么多垃圾尝试推进纪元并回收一次
+const COLLECT_THRESHOLD: usize = 64;
+
+/* 一个延迟释放的对象：裸指针加上对应的释放函数 */
+struct Garbage {
+    epoch: usize,
+    ptr: *mut u8,
+    destroy: unsafe fn(*mut u8),
+}
+
+// 垃圾只会被回收线程释放一次，此时已经没有其他线程能访问它
+unsafe impl Send for Garbage {}
+
+/* 回收器，每个无锁数据结构持有一个 */
+pub struct Collector {
+    epoch: CachePadded<AtomicUsize>,
+    // 线程槽位：0表示空闲，否则为(纪元 << 1) | 1
+    slots: Box<[CachePadded<AtomicUsize>]>,
+    garbage: Mutex<Vec<Garbage>>,
+}
+
+impl Collector {
+    // 构造函数
+    pub fn new() -> Self {
+        Self {
+            epoch: CachePadded(AtomicUsize::new(0)),
+            slots: (0..SLOTS).map(|_| CachePadded(AtomicUsize::new(0))).collect(),
+            garbage: Mutex::new(Vec::new()),
+        }
+    }
+
+    /* 进入临界区：在Guard存活期间，通过它读到的共享节点都不会被释放 */
+    pub fn pin(&self) -> Guard<'_> {
+        // 不同线程从不同的槽位开始查找，减少冲突
+        let start = thread_hint();
+        loop {
+            for i in 0..SLOTS {
+                let slot = &self.slots[(start + i) % SLOTS];
+                let epoch = self.epoch.load(Ordering::SeqCst);
+                if slot
+                    .compare_exchange(0, (epoch << 1) | 1, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+                {
+                    return Guard {
+                        collector: self,
+                        slot: (start + i) % SLOTS,
+                    };
+                }
+            }
+            thread::yield_now();
+        }
+    }
+
+    // 当前全局纪元
+    pub fn epoch(&self) -> usize {
+        self.epoch.load(Ordering::SeqCst)
+    }
+
+    // 尚未释放的垃圾数量
+    pub fn pending(&self) -> usize {
+        self.garbage.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /* 若所有pin住的线程都已观察到当前纪元，则将全局纪元加1 */
+    fn try_advance(&self) -> usize {
+        let global = self.epoch.load(Ordering::SeqCst);
+        for slot in self.slots.iter() {
+            let state = slot.load(Ordering::SeqCst);
+            if state & 1 == 1 && state >> 1 != global {
+                return global;
+            }
+        }
+        match self
+            .epoch
+            .compare_exchange(global, global + 1, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => global + 1,
+            Err(current) => current,
+        }
+    }
+
+    /* 推进纪元，并释放所有至少落后两个纪元的垃圾 */
+    pub fn collect(&self) {
+        let global = self.try_advance();
+        let ready: Vec<Garbage> = {
+            let mut garbage = self.garbage.lock().unwrap_or_else(|e| e.into_inner());
+            let (ready, pending) = garbage.drain(..).partition(|g| g.epoch + 2 <= global);
+            *garbage = pending;
+            ready
+        };
+        for g in ready {
+            // SAFETY: 丢弃该垃圾之后已经过去了两个纪元，不会再有线程访问它
+            unsafe { (g.destroy)(g.ptr) };
+        }
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Collector {
+    fn drop(&mut self) {
+        // 回收器被丢弃时不可能还有Guard存活，剩余的垃圾可以全部释放
+        let garbage = self.garbage.get_mut().unwrap_or_else(|e| e.into_inner());
+        for g in garbage.drain(..) {
+            // SAFETY: 同上
+            unsafe { (g.destroy)(g.ptr) };
+        }
+    }
+}
+
+/* pin()返回的守卫，被丢弃时离开临界区 */
+pub struct Guard<'a> {
+    collector: &'a Collector,
+    slot: usize,
+}
+
+impl Guard<'_> {
+    /// 延迟释放一个由Box::into_raw得到的指针。
+    ///
+    /// # Safety
+    ///
+    /// `ptr`必须来自`Box::into_raw`，已经无法再从共享结构中读到，并且只能被延迟释放一次。
+    pub unsafe fn defer_destroy<T>(&self, ptr: *mut T) {
+        unsafe fn destroy<T>(ptr: *mut u8) {
+            drop(Box::from_raw(ptr as *mut T));
+        }
+        let epoch = self.collector.epoch.load(Ordering::SeqCst);
+        let len = {
+            let mut garbage = self.collector.garbage.lock().unwrap_or_else(|e| e.into_inner());
+            garbage.push(Garbage {
+                epoch,
+                ptr: ptr as *mut u8,
+                destroy: destroy::<T>,
+            });
+            garbage.len()
+        };
+        if len % COLLECT_THRESHOLD == 0 {
+            self.collector.collect();
+        }
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.collector.slots[self.slot].store(0, Ordering::SeqCst);
+    }
+}
+
+/* 为当前线程分配一个固定的起始槽位 */
+fn thread_hint() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static HINT: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    HINT.with(|hint| *hint)
+}
@@ -0,0 +1,139 @@
+/* 极简的单线程执行器
+    Future只有被poll才会推进。执行器维护一个就绪队列：任务被唤醒时把自己的编号放入就绪队列，执行器依次取出并poll。
+    所有任务都在调用run/block_on的线程上执行，因此任务本身不需要实现Send。 */
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+/* 任务的唤醒器：把任务编号放回就绪队列 */
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+    thread: Thread, // 执行器所在的线程，就绪队列为空时它可能在睡眠
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push_back(self.id);
+        self.thread.unpark();
+    }
+}
+
+/* 单线程执行器 */
+pub struct LocalExecutor {
+    tasks: RefCell<Vec<Option<LocalTask>>>,
+    spawned: RefCell<Vec<LocalTask>>, // 运行期间新提交的任务
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl LocalExecutor {
+    // 构造函数
+    pub fn new() -> Self {
+        Self {
+            tasks: RefCell::new(Vec::new()),
+            spawned: RefCell::new(Vec::new()),
+            ready: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /* 提交一个任务，它会在下一次run_until_stalled或block_on时开始执行 */
+    pub fn spawn<F: Future<Output = ()> + 'static>(&self, future: F) {
+        self.spawned.borrow_mut().push(Box::pin(future));
+    }
+
+    // 尚未完成的任务数量
+    pub fn pending(&self) -> usize {
+        let tasks = self.tasks.borrow();
+        tasks.iter().filter(|task| task.is_some()).count() + self.spawned.borrow().len()
+    }
+
+    // 把新提交的任务移入任务表，并标记为就绪
+    fn admit_spawned(&self) {
+        let spawned: Vec<LocalTask> = self.spawned.borrow_mut().drain(..).collect();
+        let mut tasks = self.tasks.borrow_mut();
+        let mut ready = self.ready.lock().unwrap_or_else(|e| e.into_inner());
+        for task in spawned {
+            ready.push_back(tasks.len());
+            tasks.push(Some(task));
+        }
+    }
+
+    fn waker(&self, id: usize) -> Waker {
+        Waker::from(Arc::new(TaskWaker {
+            id,
+            ready: Arc::clone(&self.ready),
+            thread: thread::current(),
+        }))
+    }
+
+    /* 不断poll就绪的任务，直到没有任务就绪为止，返回尚未完成的任务数量 */
+    pub fn run_until_stalled(&self) -> usize {
+        loop {
+            self.admit_spawned();
+            let next = self.ready.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+            let Some(id) = next else {
+                return self.pending();
+            };
+            // 取出任务后再poll，这样任务内部可以调用spawn
+            let task = self.tasks.borrow_mut()[id].take();
+            let Some(mut task) = task else {
+                continue; // 已完成的任务被重复唤醒
+            };
+            let waker = self.waker(id);
+            let mut cx = Context::from_waker(&waker);
+            if task.as_mut().poll(&mut cx).is_pending() {
+                self.tasks.borrow_mut()[id] = Some(task);
+            }
+        }
+    }
+
+    /* 运行future直到完成，期间同时推进其他任务；没有任何任务就绪时让线程睡眠，等待其他线程唤醒 */
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let woken = Arc::new(Mutex::new(VecDeque::new()));
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id: usize::MAX,
+            ready: Arc::clone(&woken),
+            thread: thread::current(),
+        }));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            self.run_until_stalled();
+            // 主任务被唤醒则重新poll，否则睡眠等待
+            let main_woken = woken.lock().unwrap_or_else(|e| e.into_inner()).pop_front().is_some();
+            let ready = !self.ready.lock().unwrap_or_else(|e| e.into_inner()).is_empty();
+            if !main_woken && !ready {
+                thread::park();
+            }
+            woken.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+}
+
+impl Default for LocalExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* 在当前线程上运行一个future直到完成 */
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    LocalExecutor::new().block_on(future)
+}
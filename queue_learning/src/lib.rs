@@ -0,0 +1,12 @@
+/* 队列的实现 */
+pub mod array_queue;
+pub mod async_channel;
+pub mod blocking_queue;
+pub mod epoch;
+pub mod executor;
+pub mod linked_list_queue;
+pub mod ms_queue;
+pub mod rng;
+pub mod scheduler;
+pub mod simulation;
+pub mod spsc;
@@ -0,0 +1,232 @@
+/* 基于链表实现的队列
+    将链表的头节点视为队首，尾节点视为队尾，规定队尾仅可添加节点，队首仅可删除节点。
+    队列独占所有节点，头尾节点都保存为裸指针(节点由Box::into_raw分配)，这样入队和出队都是O(1)，
+    并且不再需要T: Copy，也不需要Rc<RefCell>带来的运行时借用检查。 */
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr;
+
+// 链表节点
+pub struct ListNode<T> {
+    elem: T,
+    next: *mut ListNode<T>,
+}
+
+impl<T> ListNode<T> {
+    pub fn new(elem: T) -> Self {
+        Self {
+            elem,
+            next: ptr::null_mut(),
+        }
+    }
+}
+
+// 基于链表的队列
+pub struct LinkedListQueue<T> {
+    front: *mut ListNode<T>, // 头节点，队列为空时为空指针
+    rear: *mut ListNode<T>,  // 尾节点，队列为空时为空指针
+    que_size: usize,         // 队列的长度
+    marker: PhantomData<Box<ListNode<T>>>,
+}
+
+impl<T> LinkedListQueue<T> {
+    // 构造函数
+    pub fn new() -> Self {
+        Self {
+            front: ptr::null_mut(),
+            rear: ptr::null_mut(),
+            que_size: 0,
+            marker: PhantomData,
+        }
+    }
+
+    // 获取队列的长度
+    pub fn size(&self) -> usize {
+        self.que_size
+    }
+
+    // 判断队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.que_size == 0
+    }
+
+    // 入队
+    pub fn push(&mut self, elem: T) {
+        let new_rear = Box::into_raw(Box::new(ListNode::new(elem)));
+        if self.rear.is_null() {
+            // 队列为空，头尾节点都指向新节点
+            self.front = new_rear;
+        } else {
+            // SAFETY: rear非空时指向队列所拥有的最后一个节点
+            unsafe {
+                (*self.rear).next = new_rear;
+            }
+        }
+        // 无论队列是否为空，都需要更新尾节点
+        self.rear = new_rear;
+        self.que_size += 1;
+    }
+
+    // 出队
+    pub fn pop(&mut self) -> Option<T> {
+        if self.front.is_null() {
+            return None;
+        }
+        // SAFETY: front非空时指向由Box::into_raw分配、且仅由队列持有的节点
+        let old_front = unsafe { Box::from_raw(self.front) };
+        self.front = old_front.next;
+        // 队列已空，尾节点也需要置空
+        if self.front.is_null() {
+            self.rear = ptr::null_mut();
+        }
+        self.que_size -= 1;
+        Some(old_front.elem)
+    }
+
+    // 访问队首元素
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: 节点的生命周期不短于&self
+        unsafe { self.front.as_ref().map(|node| &node.elem) }
+    }
+
+    // 访问并修改队首元素
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: &mut self保证了对节点的独占访问
+        unsafe { self.front.as_mut().map(|node| &mut node.elem) }
+    }
+
+    // 从队首到队尾遍历
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            // SAFETY: 节点的生命周期不短于&self
+            next: unsafe { self.front.as_ref() },
+        }
+    }
+
+    // 从队首到队尾可变遍历
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            // SAFETY: &mut self保证了对节点的独占访问
+            next: unsafe { self.front.as_mut() },
+        }
+    }
+
+    // 将链表转化成Array并返回
+    pub fn to_array(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        // 迭代遍历，避免长链表递归导致栈溢出
+        self.iter().cloned().collect()
+    }
+}
+
+// 队列独占所有节点，因此与Box<T>一样可以在线程间转移
+unsafe impl<T: Send> Send for LinkedListQueue<T> {}
+unsafe impl<T: Sync> Sync for LinkedListQueue<T> {}
+
+impl<T> Default for LinkedListQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedListQueue<T> {
+    fn drop(&mut self) {
+        // 逐个出队以释放所有节点
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T> FromIterator<T> for LinkedListQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for LinkedListQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+/* 不可变迭代器 */
+pub struct Iter<'a, T> {
+    next: Option<&'a ListNode<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            // SAFETY: 后继节点与当前节点同属一个队列
+            self.next = unsafe { node.next.as_ref() };
+            &node.elem
+        })
+    }
+}
+
+/* 可变迭代器 */
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut ListNode<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.take().map(|node| {
+            // SAFETY: 每个节点只会被可变借用一次
+            self.next = unsafe { node.next.as_mut() };
+            &mut node.elem
+        })
+    }
+}
+
+/* 按值迭代器，依次出队 */
+pub struct IntoIter<T>(LinkedListQueue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size(), Some(self.0.size()))
+    }
+}
+
+impl<T> IntoIterator for LinkedListQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedListQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedListQueue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
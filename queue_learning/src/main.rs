@@ -1,4 +1,24 @@
-use std::{cell::RefCell, collections::VecDeque, rc::Rc, thread::sleep_ms};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+use queue_learning::array_queue::{ArrayQueue, OverflowPolicy, QueueFull};
+use queue_learning::async_channel::{channel, SendError, TryRecvError, TrySendError};
+use queue_learning::blocking_queue::{BlockingQueue, PopError, PushError};
+use queue_learning::executor::{block_on, LocalExecutor};
+use queue_learning::linked_list_queue::LinkedListQueue;
+use queue_learning::ms_queue::MsQueue;
+use queue_learning::rng::{Distribution, Rng};
+use queue_learning::scheduler::{comparison_table, parse_process_table, schedule, Policy, Process};
+use queue_learning::simulation::{simulate, summary_csv, Discipline, SimConfig};
+use queue_learning::spsc::SpscRing;
 
 fn main() {
     /* 初始化双向队列 */
@@ -29,182 +49,728 @@ fn main() {
     /* 判断队列是否为空 */
     let is_empty = deque.is_empty();
     println!("{}", is_empty);
-}
 
-// 先实现一个链表节点
-#[allow(dead_code)]
-pub struct ListNode<T>{
-    elem: T,
-    next: Option<Rc<RefCell<ListNode<T>>>>,
-}
+    /* 基于链表实现的队列 */
+    let mut queue: LinkedListQueue<String> = ["1", "3", "2"].iter().map(|s| s.to_string()).collect();
+    queue.push("5".to_string());
+    if let Some(front) = queue.peek_mut() {
+        front.push('!');
+    }
+    println!("{:?}", queue.to_array());
+    println!("{:?}", queue.pop());
 
-#[allow(dead_code)]
-impl <T> ListNode<T> {
-    pub fn new(elem: T) -> Self {
-        Self{
-            elem,
-            next: None,
+    /* 基于环形数组实现的队列 */
+    let mut queue = ArrayQueue::new(3);
+    for elem in [1, 3, 2, 5] {
+        if let Err(err) = queue.push(elem) {
+            println!("{}: {}", err, err.0);
         }
     }
-}
+    println!("{:?}", queue.pop());
+    println!("{:?}", queue);
+
+    check_linked_list_queue();
+    check_array_queue();
+    check_blocking_queue();
+    check_spsc_ring();
+    check_ms_queue();
+    check_async_channel();
 
-// 基于链表的实现
-#[allow(dead_code)]
-pub struct LinkedListQueue<T>{
-    front: Option<Rc<RefCell<ListNode<T>>>>,    // 头节点
-    rear: Option<Rc<RefCell<ListNode<T>>>>,     // 尾节点
-    que_size: usize,                              // 队列的长度
+    simulate_queues();
+    check_simulation();
+
+    compare_schedulers();
+    check_scheduler();
 }
 
-// 实现特征
-impl<T: Copy> LinkedListQueue<T> {
-    // 构造函数
-    pub fn new() -> Self{
-        Self { 
-            front: None, 
-            rear: None, 
-            que_size: 0, 
+/* 模型测试：用一串伪随机操作同时驱动LinkedListQueue和VecDeque，两者的行为应当完全一致 */
+fn check_linked_list_queue() {
+    let mut queue = LinkedListQueue::new();
+    let mut model = VecDeque::new();
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    for step in 0..10_000u64 {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        match (seed >> 33) % 5 {
+            0 | 1 => {
+                queue.push(step);
+                model.push_back(step);
+            }
+            2 => assert_eq!(queue.pop(), model.pop_front()),
+            3 => {
+                if let Some(front) = queue.peek_mut() {
+                    *front += 1;
+                }
+                if let Some(front) = model.front_mut() {
+                    *front += 1;
+                }
+            }
+            _ => assert_eq!(queue.peek(), model.front()),
         }
+        assert_eq!(queue.size(), model.len());
+        assert_eq!(queue.is_empty(), model.is_empty());
     }
+    assert!(queue.iter().eq(model.iter()));
+    assert_eq!(queue.to_array(), Vec::from(model.clone()));
+    for elem in queue.iter_mut() {
+        *elem *= 2;
+    }
+    assert!(queue.into_iter().eq(model.into_iter().map(|x| x * 2)));
+    println!("LinkedListQueue与VecDeque的行为一致");
+}
+
+/* 基于链表的实现 */
+// 见 linked_list_queue.rs
 
-    // 获取队列的长度
-    pub fn size(&self) -> usize {
-        return self.que_size;
+/* 基于数组的实现 */
+// 见 array_queue.rs
+
+/* ArrayQueue的三种溢出策略，以及跨越数组尾部后的as_slices、iter和drain */
+fn check_array_queue() {
+    // Reject：已满时原样返回元素
+    let mut queue = ArrayQueue::new(4);
+    for elem in 0..4 {
+        assert_eq!(queue.push(elem), Ok(None));
+    }
+    assert_eq!(queue.push(4), Err(QueueFull(4)));
+    assert_eq!(queue.pop(), Some(0));
+    assert_eq!(queue.pop(), Some(1));
+    queue.push(4).unwrap();
+    queue.push(5).unwrap();
+    // 此时队尾已越过数组尾部
+    assert_eq!(queue.as_slices(), (&[2, 3][..], &[4, 5][..]));
+    assert!(queue.iter().copied().eq(2..6));
+    if let Some(front) = queue.peek_mut() {
+        *front = 20;
     }
+    assert_eq!(queue.peek(), Some(&20));
+    assert_eq!(queue.drain().collect::<Vec<_>>(), vec![20, 3, 4, 5]);
+    assert!(queue.is_empty());
 
-    // 判断队列是否为空
-    pub fn is_empty(&self) -> bool {
-        self.que_size == 0
+    // Overwrite：已满时覆盖最旧的元素并返回它
+    let mut ring = ArrayQueue::with_policy(3, OverflowPolicy::Overwrite);
+    let overwritten: Vec<_> = (0..7).filter_map(|x| ring.push(x).unwrap()).collect();
+    assert_eq!(overwritten, vec![0, 1, 2, 3]);
+    assert_eq!(ring.to_array(), vec![4, 5, 6]);
+    assert_eq!(ring.capacity(), 3);
+
+    // Grow：已满时扩容，元素重新排列到数组头部
+    let mut growing = ArrayQueue::with_policy(2, OverflowPolicy::Grow);
+    growing.push(0).unwrap();
+    growing.push(1).unwrap();
+    growing.pop();
+    growing.push(2).unwrap();
+    growing.push(3).unwrap();
+    assert_eq!(growing.capacity(), 4);
+    assert_eq!(growing.as_slices(), (&[1, 2, 3][..], &[][..]));
+
+    // 被部分消费的drain在丢弃时清空剩余元素
+    let mut strings = ArrayQueue::new(3);
+    for s in ["a", "b", "c"] {
+        strings.push(s.to_string()).unwrap();
     }
+    assert_eq!(strings.drain().next(), Some("a".to_string()));
+    assert!(strings.is_empty());
+    println!("ArrayQueue的溢出策略符合预期");
+}
 
-    // 入队
-    pub fn push(&mut self, elem: T) {
-        let new_rear = Rc::new(RefCell::new(ListNode::new(elem)));
-        match self.rear.take() {
-            Some(old_rear) => {
-                old_rear.borrow_mut().next = Some(new_rear);
-            },
-            None => {
-                self.front = Some(new_rear.clone());
-                self.rear = Some(new_rear);
-            },
-        }
-        self.que_size += 1;
-    }
-
-    // 出队
-    pub fn pop(&mut self) -> Option<T> {
-        self.front.take().map(| old_front| {
-            match old_front.borrow_mut().next.take(){
-                Some(new_front) => {
-                    self.front = Some(new_front);
-                },
-                None => {
-                    self.rear.take();
+/* 多生产者多消费者：每个元素恰好被取出一次，且同一生产者的元素保持先进先出 */
+fn check_blocking_queue() {
+    const PRODUCERS: usize = 4;
+    const CONSUMERS: usize = 4;
+    const PER_PRODUCER: usize = 20_000;
+
+    let queue = Arc::new(BlockingQueue::new(64));
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|p| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                for i in 0..PER_PRODUCER {
+                    queue.push((p, i)).unwrap();
                 }
-            }
-        self.que_size -= 1;
-        Rc::try_unwrap(old_front).ok().unwrap().into_inner().elem
-    })
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..CONSUMERS)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                let mut last = [None; PRODUCERS];
+                let mut count = 0;
+                while let Some((p, i)) = queue.pop() {
+                    // 单个消费者看到的同一生产者的序号必须递增
+                    assert!(last[p].is_none_or(|prev| prev < i));
+                    last[p] = Some(i);
+                    count += 1;
+                }
+                count
+            })
+        })
+        .collect();
+    for producer in producers {
+        producer.join().unwrap();
     }
+    // 生产者全部结束后关闭队列，消费者取完剩余元素后退出
+    queue.close();
+    let total: usize = consumers.into_iter().map(|c| c.join().unwrap()).sum();
+    assert_eq!(total, PRODUCERS * PER_PRODUCER);
 
-    // 访问队首元素
-    pub fn peek(&self) -> Option<&Rc<RefCell<ListNode<T>>>> {
-        self.front.as_ref()
-    }
+    // 关闭后拒绝入队，阻塞中的线程会被唤醒
+    let queue = Arc::new(BlockingQueue::new(1));
+    queue.push(1).unwrap();
+    assert_eq!(queue.try_push(2), Err(PushError::Full(2)));
+    assert_eq!(queue.push_timeout(2, Duration::from_millis(10)), Err(PushError::Timeout(2)));
+    let blocked = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || queue.push(3))
+    };
+    thread::sleep(Duration::from_millis(20));
+    queue.close();
+    assert_eq!(blocked.join().unwrap(), Err(PushError::Closed(3)));
+    assert_eq!(queue.try_pop(), Ok(1));
+    assert_eq!(queue.try_pop(), Err(PopError::Closed));
+    assert_eq!(queue.pop(), None);
 
-    // 将链表转化成Array并返回
-    pub fn to_array(&self, head: Option<&Rc<RefCell<ListNode<T>>>>) -> Vec<T> {
-        if let Some(node) = head{
-            let mut elems = self.to_array(node.borrow().next.as_ref());
-            elems.insert(0, node.borrow().elem);
-            return elems;
+    let empty: BlockingQueue<i32> = BlockingQueue::new(2);
+    assert_eq!(empty.pop_timeout(Duration::from_millis(10)), Err(PopError::Timeout));
+    assert_eq!(empty.try_pop(), Err(PopError::Empty));
+    println!("BlockingQueue在多生产者多消费者下工作正常");
+}
+
+/* 单生产者单消费者：消费者按顺序收到全部元素，混合使用单个和批量操作 */
+fn check_spsc_ring() {
+    const COUNT: u64 = 200_000;
+    let (mut producer, mut consumer) = SpscRing::new(100).split();
+    let handle = thread::spawn(move || {
+        let mut next = 0;
+        while next < COUNT {
+            if next % 3 == 0 {
+                let mut batch = next..(next + 17).min(COUNT);
+                next += producer.push_batch(&mut batch) as u64;
+            } else if producer.push(next).is_ok() {
+                next += 1;
+            } else {
+                thread::yield_now();
+            }
+        }
+    });
+    let mut expected = 0;
+    let mut batch = Vec::new();
+    while expected < COUNT {
+        if consumer.pop_batch(&mut batch, 32) == 0 {
+            match consumer.pop() {
+                Some(elem) => batch.push(elem),
+                None => thread::yield_now(),
+            }
         }
-        return Vec::new();
+        for elem in batch.drain(..) {
+            assert_eq!(elem, expected);
+            expected += 1;
+        }
+    }
+    handle.join().unwrap();
+    assert!(consumer.is_abandoned());
+    assert_eq!(consumer.pop(), None);
+
+    // 未被消费的元素随队列一起释放
+    let counter = Arc::new(());
+    let (mut producer, consumer) = SpscRing::new(4).split();
+    for _ in 0..4 {
+        producer.push(Arc::clone(&counter)).unwrap();
     }
+    assert!(producer.push(Arc::clone(&counter)).is_err());
+    drop(producer);
+    drop(consumer);
+    assert_eq!(Arc::strong_count(&counter), 1);
+    println!("SpscRing按顺序传递了全部元素");
 }
 
-/* 基于数组的实现 */
-// 使用环形数组，让front或rear在越过数组尾部时，直接回到数组头部继续遍历。这种周期性规律可以通过“取余操作”来实现
-
-// 基于环形数组实现的队列
-#[allow(dead_code)]
-struct ArrayQueue<T>{
-    elems: Vec<T>,      // 用于存储队列元素的数组
-    front: usize,           // 队首指针，指向队首元素
-    que_size: usize,    // 队列长度
-    que_capacity: usize,// 队列容量
+/* 出队操作的历史记录：[invoke, response]为操作的逻辑时间区间 */
+struct PopRecord {
+    invoke: u64,
+    response: u64,
+    producer: usize,
+    seq: usize,
 }
 
-#[allow(dead_code)]
-impl<T: Clone + Copy> ArrayQueue<T>  {
-    // 构造函数
-    pub fn new(capacity: usize) -> Self {
-        Self { 
-            elems: Vec::new(), 
-            front: 0, 
-            que_size: 0, 
-            que_capacity: capacity 
+/* 线性一致性压力测试
+    多个生产者和消费者并发操作MsQueue，每次出队都用全局逻辑时钟记录调用和返回时刻。检查：
+        1.每个元素恰好被取出一次；
+        2.同一生产者的元素a先于b入队，则b的出队不能在a的出队开始之前就已经完成(按生产者的先进先出)。 */
+fn check_ms_queue() {
+    const PRODUCERS: usize = 4;
+    const CONSUMERS: usize = 4;
+    const PER_PRODUCER: usize = 20_000;
+
+    let queue = Arc::new(MsQueue::new());
+    let clock = Arc::new(AtomicU64::new(0));
+    let done = Arc::new(AtomicU64::new(0));
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|p| {
+            let queue = Arc::clone(&queue);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                for seq in 0..PER_PRODUCER {
+                    queue.push((p, seq));
+                }
+                done.fetch_add(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..CONSUMERS)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let clock = Arc::clone(&clock);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut history = Vec::new();
+                loop {
+                    // 先读取生产者是否全部结束，再出队，避免漏掉最后的元素
+                    let finished = done.load(Ordering::SeqCst) == PRODUCERS as u64;
+                    let invoke = clock.fetch_add(1, Ordering::SeqCst);
+                    match queue.pop() {
+                        Some((producer, seq)) => {
+                            let response = clock.fetch_add(1, Ordering::SeqCst);
+                            history.push(PopRecord { invoke, response, producer, seq });
+                        }
+                        None if finished => break,
+                        None => thread::yield_now(),
+                    }
+                }
+                history
+            })
+        })
+        .collect();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    let mut by_producer: Vec<Vec<PopRecord>> = (0..PRODUCERS).map(|_| Vec::new()).collect();
+    for consumer in consumers {
+        for record in consumer.join().unwrap() {
+            by_producer[record.producer].push(record);
+        }
+    }
+    for records in &mut by_producer {
+        records.sort_by_key(|r| r.seq);
+        assert_eq!(records.len(), PER_PRODUCER);
+        assert!(records.iter().enumerate().all(|(i, r)| r.seq == i));
+        // 只需检查相邻的元素：后一个元素的出队不能在前一个元素的出队开始之前完成
+        for pair in records.windows(2) {
+            assert!(pair[1].response > pair[0].invoke, "违反了按生产者的先进先出顺序");
         }
     }
+    assert!(queue.is_empty());
+    println!("MsQueue的出队历史满足按生产者的先进先出");
+}
 
-    // 获取队列长度
-    pub fn size(&self) -> usize{
-        self.que_size
-    } 
+/* 记录被唤醒次数的Waker */
+struct CountingWaker(AtomicUsize);
 
-    // 获取队列容量
-    pub fn capacity(&self) -> usize {
-        self.que_capacity
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
     }
+}
+
+fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    (Arc::clone(&counter), Waker::from(counter))
+}
+
+fn check_async_channel() {
+    // 多个发送任务和一个接收任务在单线程执行器上交替运行
+    let executor = LocalExecutor::new();
+    let (sender, mut receiver) = channel(2);
+    for id in 0..3 {
+        let sender = sender.clone();
+        executor.spawn(async move {
+            for seq in 0..100 {
+                sender.send((id, seq)).await.unwrap();
+            }
+        });
+    }
+    drop(sender);
+    let received = Rc::new(RefCell::new(Vec::new()));
+    {
+        let received = Rc::clone(&received);
+        executor.spawn(async move {
+            // 所有发送者结束后recv返回None
+            while let Some(elem) = receiver.recv().await {
+                received.borrow_mut().push(elem);
+            }
+        });
+    }
+    assert_eq!(executor.run_until_stalled(), 0);
+    let received = received.borrow();
+    assert_eq!(received.len(), 300);
+    for id in 0..3 {
+        let seqs: Vec<_> = received.iter().filter(|(i, _)| *i == id).map(|(_, s)| *s).collect();
+        assert_eq!(seqs, (0..100).collect::<Vec<_>>());
+    }
+
+    // 取消安全：被唤醒的send Future在完成前被丢弃，元素不会被发送，唤醒机会转交给下一个发送者
+    let (sender, mut receiver) = channel(1);
+    sender.try_send(0).unwrap();
+    assert_eq!(sender.try_send(9), Err(TrySendError::Full(9)));
+    let (count_a, waker_a) = counting_waker();
+    let (count_b, waker_b) = counting_waker();
+    let mut send_a = Box::pin(sender.send(1));
+    let mut send_b = Box::pin(sender.send(2));
+    assert!(send_a.as_mut().poll(&mut Context::from_waker(&waker_a)).is_pending());
+    assert!(send_b.as_mut().poll(&mut Context::from_waker(&waker_b)).is_pending());
+    assert_eq!(receiver.try_recv(), Ok(0));
+    assert_eq!(count_a.0.load(Ordering::SeqCst), 1);
+    assert_eq!(count_b.0.load(Ordering::SeqCst), 0);
+    drop(send_a);
+    assert_eq!(count_b.0.load(Ordering::SeqCst), 1);
+    assert_eq!(send_b.as_mut().poll(&mut Context::from_waker(&waker_b)), Poll::Ready(Ok(())));
+    assert_eq!(receiver.try_recv(), Ok(2));
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
 
-    // 判断队列是否为空
-    pub fn is_empty(&self) -> bool {
-        self.que_size == 0
+    // 取消recv不会丢失元素
+    {
+        let (_, waker) = counting_waker();
+        let mut recv = pin!(receiver.recv());
+        assert!(recv.as_mut().poll(&mut Context::from_waker(&waker)).is_pending());
     }
+    sender.try_send(3).unwrap();
+    assert_eq!(receiver.try_recv(), Ok(3));
 
-    // 入队
-    pub fn push(&mut self, elem: T) {
-        if self.que_size == self.capacity(){
-            println!("队列已满");
-            return;
+    // 接收者关闭通道：发送失败，剩余元素仍可取出
+    sender.try_send(4).unwrap();
+    receiver.close();
+    assert_eq!(block_on(sender.send(5)), Err(SendError(5)));
+    assert_eq!(sender.try_send(6), Err(TrySendError::Closed(6)));
+    assert_eq!(block_on(receiver.recv()), Some(4));
+    assert_eq!(block_on(receiver.recv()), None);
+
+    // 跨线程：接收者睡眠等待，另一个线程的发送会唤醒它
+    let (sender, mut receiver) = channel(4);
+    let handle = thread::spawn(move || {
+        for i in 0..1000 {
+            block_on(sender.send(i)).unwrap();
         }
-        // 计算队尾指针，指向队尾索引+1
-        // 通过取余操作实现rear越过数组尾部后回到头部
-        let rear = (self.front + self.que_size) % self.que_capacity;
-        // 将elem添加到队尾
-        self.elems[rear] = elem;
-        self.que_size += 1;
-    }
-
-    // 出队
-    pub fn pop(&mut self) -> T{
-        let elem = self.peek();
-        // 将队首的指针向后移动一位，如果越过尾部，则返回数组头部
-        self.front = (self.front + 1) % self.que_capacity;
-        self.que_size -= 1;
-        return elem;
-    }
-
-    // 访问队首元素
-    pub fn peek(&self) -> T {
-        if self.is_empty(){
-            panic!("index out of bounds");
+    });
+    let mut expected = 0;
+    while let Some(elem) = block_on(receiver.recv()) {
+        assert_eq!(elem, expected);
+        expected += 1;
+    }
+    handle.join().unwrap();
+    assert_eq!(expected, 1000);
+    println!("异步通道的发送、接收、取消和关闭语义符合预期");
+}
+
+/* 用离散事件模拟比较不同的排队方案，结果以CSV输出，可以直接粘贴到表格软件中画图 */
+fn simulate_queues() {
+    // 订单处理：平均每单位时间到达1个订单，每个客服平均处理2.5个单位时间，比较客服数量的影响
+    let orders: Vec<(String, SimConfig)> = [3, 4]
+        .into_iter()
+        .map(|servers| {
+            let config = SimConfig {
+                servers,
+                arrival: Distribution::Exponential(1.0),
+                service: Distribution::Exponential(2.5),
+                horizon: 1000.0,
+                sample_interval: 100.0,
+                ..SimConfig::default()
+            };
+            (format!("订单-{}个客服", servers), config)
+        })
+        .collect();
+    // 打印机：一台打印机，任务分3个优先级，比较三种排队规则
+    let printers: Vec<(String, SimConfig)> = [
+        ("打印机-FIFO", Discipline::Fifo),
+        ("打印机-优先级", Discipline::Priority),
+        ("打印机-轮转", Discipline::RoundRobin { quantum: 1.0 }),
+    ]
+    .into_iter()
+    .map(|(name, discipline)| {
+        let config = SimConfig {
+            arrival: Distribution::Exponential(5.0),
+            service: Distribution::Uniform(1.0, 7.0),
+            priority_levels: 3,
+            discipline,
+            ..SimConfig::default()
+        };
+        (name.to_string(), config)
+    })
+    .collect();
+
+    let runs: Vec<(String, _)> = orders
+        .iter()
+        .chain(printers.iter())
+        .map(|(name, config)| (name.clone(), simulate(config)))
+        .collect();
+    let rows: Vec<(&str, _)> = runs.iter().map(|(name, r)| (name.as_str(), r)).collect();
+    print!("{}", summary_csv(&rows));
+    for (name, report) in &runs[orders.len()..] {
+        println!("{} 各优先级平均等待 = {:.2?}", name, report.avg_wait_by_priority);
+    }
+    print!("{}", runs[0].1.timeline_csv());
+}
+
+/* 离散事件模拟的检查：确定性输入、排队论公式与利特尔法则 */
+fn check_simulation() {
+    // 相同的种子得到完全相同的结果
+    let config = SimConfig::default();
+    assert_eq!(simulate(&config), simulate(&config));
+    let other = simulate(&SimConfig { seed: 2, ..config.clone() });
+    assert_ne!(simulate(&config).avg_wait, other.avg_wait);
+
+    // D/D/1：每隔1到达一位顾客，服务0.5，永远不需要等待
+    let report = simulate(&SimConfig {
+        arrival: Distribution::Constant(1.0),
+        service: Distribution::Constant(0.5),
+        horizon: 100.0,
+        ..SimConfig::default()
+    });
+    assert_eq!(report.served, 99);
+    assert_eq!(report.avg_wait, 0.0);
+    assert_eq!(report.max_queue_len, 1);
+    assert!((report.end_time - 99.5).abs() < 1e-9);
+    assert!((report.utilization - 49.5 / 99.5).abs() < 1e-9);
+
+    // M/M/1：到达率1，服务率2，理论平均等待时间 = ρ / (μ - λ) = 0.5
+    let report = simulate(&SimConfig {
+        arrival: Distribution::Exponential(1.0),
+        service: Distribution::Exponential(0.5),
+        horizon: 50_000.0,
+        ..SimConfig::default()
+    });
+    assert!((report.avg_wait - 0.5).abs() < 0.05, "{}", report.avg_wait);
+    assert!((report.utilization - 0.5).abs() < 0.02, "{}", report.utilization);
+
+    for discipline in [
+        Discipline::Fifo,
+        Discipline::Priority,
+        Discipline::RoundRobin { quantum: 0.3 },
+    ] {
+        let config = SimConfig {
+            servers: 2,
+            arrival: Distribution::Exponential(1.0),
+            service: Distribution::Normal {
+                mean: 1.8,
+                std_dev: 0.5,
+            },
+            priority_levels: 3,
+            discipline,
+            horizon: 20_000.0,
+            ..SimConfig::default()
+        };
+        let report = simulate(&config);
+        // 利特尔法则：平均队列长度 = 有效到达率 * 平均等待时间
+        let rate = report.served as f64 / report.end_time;
+        let little = rate * report.avg_wait;
+        assert!(
+            (report.avg_queue_len - little).abs() < 0.01 * little.max(1.0),
+            "{:?}: {} vs {}",
+            discipline,
+            report.avg_queue_len,
+            little
+        );
+        assert_eq!(report.server_utilization.len(), 2);
+        assert!(report.server_utilization.iter().all(|&u| u > 0.5 && u <= 1.0));
+        if discipline == Discipline::Priority {
+            // 高优先级的顾客等待更短
+            let waits = &report.avg_wait_by_priority;
+            assert!(waits[0] < waits[1] && waits[1] < waits[2], "{:?}", waits);
         }
-        self.elems[self.front as usize]
     }
 
-    // 返回数组
-    fn to_array(&self) -> Vec<T> {
-        let cap = self.que_capacity;
-        let mut j = self.front;
-        let mut arr = Vec::with_capacity(self.size());
-        for i in 0..self.que_size{
-            arr[i] = self.elems[(j%cap) as usize];
-            j += 1;
+    // 时间片大于任何服务时间时，轮转退化为先进先出
+    let fifo = SimConfig {
+        service: Distribution::Uniform(0.1, 1.5),
+        ..SimConfig::default()
+    };
+    let rr = SimConfig {
+        discipline: Discipline::RoundRobin { quantum: 10.0 },
+        ..fifo.clone()
+    };
+    assert_eq!(simulate(&fifo), simulate(&rr));
+
+    // CSV：表头加每次模拟一行；采样一直持续到最后一位顾客离开
+    let report = simulate(&fifo);
+    let csv = summary_csv(&[("a", &report), ("b", &report)]);
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.starts_with("scenario,served,avg_wait"));
+    let timeline = report.timeline_csv();
+    assert_eq!(timeline.lines().count(), report.samples.len() + 1);
+    assert!(report.samples.last().unwrap().time >= report.end_time);
+    println!("离散事件模拟的结果符合排队论的预期");
+}
+
+/* 同一张进程表在不同调度策略下的表现 */
+const PROCESS_TABLE: &str = "
+# 名称 到达时刻 运行时间 优先级
+P1 0 8 3
+P2 1 4 1
+P3 2 9 4
+P4 3 5 2
+P5 6 2 1
+";
+
+fn policies() -> Vec<Policy> {
+    vec![
+        Policy::Fcfs,
+        Policy::Sjf,
+        Policy::Srtf,
+        Policy::RoundRobin { quantum: 3 },
+        Policy::Priority {
+            preemptive: false,
+            aging: 0,
+        },
+        Policy::Priority {
+            preemptive: true,
+            aging: 5,
+        },
+        Policy::Mlfq {
+            quanta: vec![2, 4, 8],
+            boost: 20,
+        },
+    ]
+}
+
+fn compare_schedulers() {
+    let procs = parse_process_table(PROCESS_TABLE).expect("内置的进程表格式正确");
+    let schedules: Vec<_> = policies().iter().map(|p| schedule(&procs, p)).collect();
+    print!("{}", comparison_table(&schedules));
+    for s in &schedules {
+        println!("{:<24} {}", s.policy, s.gantt());
+    }
+}
+
+/* 调度模拟的检查：教材中的经典例子，以及对随机进程表都成立的不变量 */
+fn check_scheduler() {
+    let table = |rows: &[(u32, u32, u32)]| -> Vec<Process> {
+        rows.iter()
+            .enumerate()
+            .map(|(i, &(arrival, burst, priority))| Process {
+                name: format!("P{}", i + 1),
+                arrival,
+                burst,
+                priority,
+            })
+            .collect()
+    };
+    let avg_waiting = |procs: &[Process], policy: Policy| schedule(procs, &policy).avg_waiting();
+
+    // FCFS的护航效应：长进程在前时平均等待17，在后时只有3
+    let convoy = table(&[(0, 24, 0), (0, 3, 0), (0, 3, 0)]);
+    assert_eq!(avg_waiting(&convoy, Policy::Fcfs), 17.0);
+    let reversed = table(&[(0, 3, 0), (0, 3, 0), (0, 24, 0)]);
+    assert_eq!(avg_waiting(&reversed, Policy::Fcfs), 3.0);
+    // RR(q=4)：等待时间分别为6、4、7
+    assert_eq!(avg_waiting(&convoy, Policy::RoundRobin { quantum: 4 }), 17.0 / 3.0);
+    // SJF：所有进程同时到达
+    let sjf = table(&[(0, 6, 0), (0, 8, 0), (0, 7, 0), (0, 3, 0)]);
+    assert_eq!(avg_waiting(&sjf, Policy::Sjf), 7.0);
+    // SRTF：进程陆续到达，平均等待6.5；非抢占的SJF为7.75
+    let srtf = table(&[(0, 8, 0), (1, 4, 0), (2, 9, 0), (3, 5, 0)]);
+    assert_eq!(avg_waiting(&srtf, Policy::Srtf), 6.5);
+    assert_eq!(avg_waiting(&srtf, Policy::Sjf), 7.75);
+    // 非抢占的优先级调度：平均等待8.2
+    let priority = table(&[(0, 10, 3), (0, 1, 1), (0, 2, 4), (0, 1, 5), (0, 5, 2)]);
+    let no_aging = Policy::Priority {
+        preemptive: false,
+        aging: 0,
+    };
+    assert_eq!(avg_waiting(&priority, no_aging.clone()), 8.2);
+
+    // 饥饿与老化：高优先级进程源源不断地到达，低优先级的P1没有老化时最后才运行
+    let mut rows = vec![(0, 2, 9)];
+    rows.extend((0..20).map(|i| (i * 2, 2, 1)));
+    let stream = table(&rows);
+    let starved = schedule(&stream, &no_aging);
+    assert_eq!(starved.stats[0].completion, 42);
+    let aged = schedule(
+        &stream,
+        &Policy::Priority {
+            preemptive: true,
+            aging: 2,
+        },
+    );
+    assert!(aged.stats[0].completion < 30, "{}", aged.gantt());
+
+    // MLFQ：短的交互式进程很快得到响应，长进程被降级后不会阻塞它
+    let mixed = table(&[(0, 30, 0), (5, 2, 0), (12, 1, 0)]);
+    let mlfq = Policy::Mlfq {
+        quanta: vec![2, 4, 8],
+        boost: 0,
+    };
+    let s = schedule(&mixed, &mlfq);
+    assert!(s.stats[1].response <= 1 && s.stats[2].response <= 1, "{}", s.gantt());
+    assert_eq!(schedule(&mixed, &Policy::Fcfs).stats[1].response, 25);
+    // 提升发生时P1正在第二级运行：它回到最高级并重新获得完整的时间片，继续运行1个时间单位后才轮到P2
+    let busy = table(&[(0, 20, 0), (0, 20, 0)]);
+    let boosted = schedule(
+        &busy,
+        &Policy::Mlfq {
+            quanta: vec![1, 8],
+            boost: 6,
+        },
+    );
+    let running_at = |t: u32| {
+        let slice = boosted.timeline.iter().find(|s| s.start <= t && t < s.end);
+        slice.and_then(|s| s.pid)
+    };
+    assert_eq!(
+        (running_at(5), running_at(6), running_at(7)),
+        (Some(0), Some(0), Some(1)),
+        "{}",
+        boosted.gantt()
+    );
+
+    // 进程表解析
+    assert_eq!(parse_process_table(PROCESS_TABLE).unwrap().len(), 5);
+    let procs = parse_process_table("A 0 3\n\n# 注释\nB 2 4 7").unwrap();
+    assert_eq!(procs[0].priority, 0);
+    assert_eq!(procs[1], Process { name: "B".to_string(), arrival: 2, burst: 4, priority: 7 });
+    assert_eq!(parse_process_table("A 0").unwrap_err().line, 1);
+    assert_eq!(parse_process_table("A 0 1\nB x 1").unwrap_err().line, 2);
+    assert!(parse_process_table("A 0 0").is_err());
+    assert!(parse_process_table("A 0 1\nA 1 1").is_err());
+
+    // 随机进程表上的不变量
+    let mut rng = Rng::new(7);
+    let mut next = |modulo: u64| rng.gen_range(0..modulo) as u32;
+    for _ in 0..50 {
+        let rows: Vec<(u32, u32, u32)> = (0..12).map(|_| (next(40), next(9) + 1, next(5))).collect();
+        let procs = table(&rows);
+        let total: u32 = procs.iter().map(|p| p.burst).sum();
+        let mut fcfs_sjf = None;
+        for policy in policies() {
+            let s = schedule(&procs, &policy);
+            // 时间线连续且不重叠，每个进程恰好运行burst个时间单位，并且不会在到达之前运行
+            let mut used = vec![0; procs.len()];
+            let mut at = 0;
+            for slice in &s.timeline {
+                assert_eq!(slice.start, at);
+                assert!(slice.end > slice.start);
+                at = slice.end;
+                if let Some(pid) = slice.pid {
+                    assert!(slice.start >= procs[pid].arrival);
+                    used[pid] += slice.end - slice.start;
+                }
+            }
+            for (pid, p) in procs.iter().enumerate() {
+                assert_eq!(used[pid], p.burst, "{}", s.policy);
+                assert_eq!(s.stats[pid].turnaround, s.stats[pid].waiting + p.burst);
+                assert!(s.stats[pid].response <= s.stats[pid].waiting);
+            }
+            let busy: u32 = s.timeline.iter().filter(|t| t.pid.is_some()).map(|t| t.end - t.start).sum();
+            assert_eq!(busy, total);
+            // 所有策略都不会让CPU在有进程就绪时空闲，因此完成时刻相同
+            let makespan = fcfs_sjf.get_or_insert(s.makespan());
+            assert_eq!(*makespan, s.makespan(), "{}", s.policy);
+        }
+        // 所有进程同时到达时SJF的平均等待时间最小
+        let same: Vec<Process> = procs.iter().map(|p| Process { arrival: 0, ..p.clone() }).collect();
+        let best = avg_waiting(&same, Policy::Sjf);
+        for policy in policies() {
+            assert!(best <= avg_waiting(&same, policy));
         }
-        arr
     }
+    println!("各调度策略的结果符合预期");
 }
 
 /* 队列的典型应用
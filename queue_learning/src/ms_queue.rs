@@ -0,0 +1,144 @@
+/* Michael–Scott无锁队列
+    与LinkedListQueue一样由单向链表构成，队首出队、队尾入队，但所有指针都是原子指针，多个线程可以同时操作。
+        1.链表头部始终有一个哨兵节点，head指向哨兵，真正的队首元素位于哨兵的后继节点中；队列为空时head与tail都指向哨兵。
+        2.入队：通过CAS把新节点挂到尾节点的next上，再尝试把tail移动到新节点。tail允许暂时落后一个节点，
+          其他线程发现tail落后时会帮忙把它向后推进。
+        3.出队：通过CAS把head移动到哨兵的后继节点，取出其中的元素，该节点成为新的哨兵，旧哨兵交给纪元回收器延迟释放。 */
+
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::epoch::Collector;
+use crate::spsc::CachePadded;
+
+/* 链表节点，哨兵节点的elem未初始化 */
+struct ListNode<T> {
+    elem: MaybeUninit<T>,
+    next: AtomicPtr<ListNode<T>>,
+}
+
+impl<T> ListNode<T> {
+    fn new(elem: MaybeUninit<T>) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            elem,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+/* 无锁多生产者多消费者队列 */
+pub struct MsQueue<T> {
+    front: CachePadded<AtomicPtr<ListNode<T>>>, // 指向哨兵节点
+    rear: CachePadded<AtomicPtr<ListNode<T>>>,  // 指向尾节点(可能暂时落后一个节点)
+    collector: Collector,
+}
+
+unsafe impl<T: Send> Send for MsQueue<T> {}
+unsafe impl<T: Send> Sync for MsQueue<T> {}
+
+impl<T> MsQueue<T> {
+    // 构造函数
+    pub fn new() -> Self {
+        let sentinel = ListNode::new(MaybeUninit::uninit());
+        Self {
+            front: CachePadded(AtomicPtr::new(sentinel)),
+            rear: CachePadded(AtomicPtr::new(sentinel)),
+            collector: Collector::new(),
+        }
+    }
+
+    // 判断队列是否为空(并发修改时只是一个瞬时的结果)
+    pub fn is_empty(&self) -> bool {
+        let _guard = self.collector.pin();
+        let front = self.front.load(Ordering::Acquire);
+        // SAFETY: pin期间front指向的节点不会被释放
+        unsafe { (*front).next.load(Ordering::Acquire).is_null() }
+    }
+
+    /* 入队 */
+    pub fn push(&self, elem: T) {
+        let node = ListNode::new(MaybeUninit::new(elem));
+        let _guard = self.collector.pin();
+        loop {
+            let rear = self.rear.load(Ordering::Acquire);
+            // SAFETY: pin期间rear指向的节点不会被释放
+            let next = unsafe { (*rear).next.load(Ordering::Acquire) };
+            if rear != self.rear.load(Ordering::Acquire) {
+                continue;
+            }
+            if !next.is_null() {
+                // tail落后了，帮忙把它向后推进
+                let _ = self
+                    .rear
+                    .compare_exchange(rear, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+            // SAFETY: 同上
+            let linked = unsafe {
+                (*rear)
+                    .next
+                    .compare_exchange(ptr::null_mut(), node, Ordering::Release, Ordering::Relaxed)
+                    .is_ok()
+            };
+            if linked {
+                // 失败说明其他线程已经帮忙推进了tail
+                let _ = self
+                    .rear
+                    .compare_exchange(rear, node, Ordering::Release, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    /* 出队，队列为空时返回None */
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.collector.pin();
+        loop {
+            let front = self.front.load(Ordering::Acquire);
+            let rear = self.rear.load(Ordering::Acquire);
+            // SAFETY: pin期间front指向的节点不会被释放
+            let next = unsafe { (*front).next.load(Ordering::Acquire) };
+            if front != self.front.load(Ordering::Acquire) {
+                continue;
+            }
+            if next.is_null() {
+                return None;
+            }
+            if front == rear {
+                // 队列非空但tail仍指向哨兵，先帮忙推进tail
+                let _ = self
+                    .rear
+                    .compare_exchange(rear, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+            if self
+                .front
+                .compare_exchange(front, next, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                // SAFETY: 只有CAS成功的线程会读取next中的元素，之后next成为哨兵，其elem视为未初始化
+                let elem = unsafe { (*next).elem.assume_init_read() };
+                // SAFETY: 旧哨兵已经从链表上摘下，且只会被释放一次
+                unsafe { guard.defer_destroy(front) };
+                return Some(elem);
+            }
+        }
+    }
+}
+
+impl<T> Default for MsQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for MsQueue<T> {
+    fn drop(&mut self) {
+        // 此时没有其他线程访问队列，先取出并丢弃剩余元素，再释放最后的哨兵
+        while self.pop().is_some() {}
+        let sentinel = *self.front.0.get_mut();
+        // SAFETY: 哨兵由ListNode::new分配，elem未初始化，不需要析构
+        unsafe { drop(Box::from_raw(sentinel)) };
+    }
+}
@@ -0,0 +1,75 @@
+/* 可复现的伪随机数
+    模拟实验需要“随机”但可以重现的输入：相同的种子总是产生相同的序列，方便对比不同的排队策略。
+    这里使用SplitMix64：状态每次加上一个固定的奇数，再经过几轮异或移位和乘法把比特充分打散。
+    SplitMix64本身由hash_algo::seeded提供，这里只是在它之上包装出浮点数、区间与各种分布。 */
+
+use std::ops::Range;
+
+use hash_algo::seeded::splitmix64;
+
+/* 带种子的伪随机数生成器 */
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    // 构造函数
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /* 生成下一个64位随机数 */
+    pub fn next_u64(&mut self) -> u64 {
+        splitmix64(&mut self.state)
+    }
+
+    /* 生成[0, 1)内均匀分布的浮点数，取高53位作为尾数 */
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /* 生成range内的整数(取余带来的偏差对模拟来说可以忽略) */
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "范围不能为空");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+}
+
+/* 到达间隔与服务时间的分布 */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Constant(f64),                      // 固定值
+    Uniform(f64, f64),                  // [a, b)内均匀分布
+    Exponential(f64),                   // 给定均值的指数分布，对应泊松到达
+    Normal { mean: f64, std_dev: f64 }, // 正态分布，负值截断为0
+}
+
+impl Distribution {
+    /* 抽取一个样本 */
+    pub fn sample(&self, rng: &mut Rng) -> f64 {
+        match *self {
+            Distribution::Constant(v) => v,
+            Distribution::Uniform(a, b) => a + (b - a) * rng.next_f64(),
+            // 逆变换采样：1 - u落在(0, 1]，取对数不会得到无穷大
+            Distribution::Exponential(mean) => -mean * (1.0 - rng.next_f64()).ln(),
+            Distribution::Normal { mean, std_dev } => {
+                // Box-Muller变换
+                let u1 = 1.0 - rng.next_f64();
+                let u2 = rng.next_f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (mean + std_dev * z).max(0.0)
+            }
+        }
+    }
+
+    // 分布的均值(正态分布忽略截断的影响)
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Distribution::Constant(v) => v,
+            Distribution::Uniform(a, b) => (a + b) / 2.0,
+            Distribution::Exponential(mean) => mean,
+            Distribution::Normal { mean, .. } => mean,
+        }
+    }
+}
@@ -0,0 +1,534 @@
+/* CPU调度模拟
+    操作系统用就绪队列管理等待CPU的进程，调度策略决定下一个运行谁、以及是否打断正在运行的进程：
+        1.先来先服务(FCFS)：就绪队列是普通的先进先出队列，不抢占。短进程排在长进程后面时要等很久(护航效应)。
+        2.短作业优先(SJF)：总是运行运行时间最短的进程，平均等待时间最小；抢占式版本称为最短剩余时间优先(SRTF)，
+          新到达的进程剩余时间更短时立即抢占。
+        3.时间片轮转(RR)：每个进程最多运行一个时间片，然后回到队尾。
+        4.优先级调度：总是运行优先级最高(数值最小)的进程。低优先级进程可能永远得不到运行(饥饿)，
+          老化(aging)让进程在就绪队列中每等待一段时间优先级就提高一级。
+        5.多级反馈队列(MLFQ)：若干个优先级从高到低的队列，时间片依次变长。新进程进入最高级队列；
+          用完整个时间片的进程被认为是CPU密集型，降到下一级；高级队列非空时抢占低级队列的进程；
+          每隔一段时间把所有进程提升回最高级，防止饥饿。
+    所有策略读取相同的进程表，以1个时间单位为步长推进，输出相同格式的指标和时间线，方便比较。 */
+
+use std::fmt::{self, Write};
+
+use heap::binary_heap::{BinaryHeap, MinComparator};
+
+use crate::array_queue::{ArrayQueue, OverflowPolicy};
+
+/* 进程表中的一行 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub name: String,
+    pub arrival: u32,  // 到达时刻
+    pub burst: u32,    // 需要运行的时间
+    pub priority: u32, // 优先级，数值越小越优先
+}
+
+/* 进程表的解析错误 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize, // 出错的行号，从1开始
+    pub msg: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "第{}行：{}", self.line, self.msg)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/* 解析进程表
+    每行依次为：名称 到达时刻 运行时间 优先级(可省略，默认为0)，以空白分隔；空行和以#开头的行被忽略。 */
+pub fn parse_process_table(input: &str) -> Result<Vec<Process>, ParseError> {
+    let mut procs: Vec<Process> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |msg: String| ParseError { line: i + 1, msg };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if !(3..=4).contains(&fields.len()) {
+            return Err(error(format!("需要3或4列，实际为{}列", fields.len())));
+        }
+        let number = |k: usize, what: &str| {
+            fields[k]
+                .parse::<u32>()
+                .map_err(|_| error(format!("{}不是非负整数：{}", what, fields[k])))
+        };
+        let process = Process {
+            name: fields[0].to_string(),
+            arrival: number(1, "到达时刻")?,
+            burst: number(2, "运行时间")?,
+            priority: if fields.len() == 4 {
+                number(3, "优先级")?
+            } else {
+                0
+            },
+        };
+        if process.burst == 0 {
+            return Err(error("运行时间必须大于0".to_string()));
+        }
+        if procs.iter().any(|p| p.name == process.name) {
+            return Err(error(format!("进程名重复：{}", process.name)));
+        }
+        procs.push(process);
+    }
+    Ok(procs)
+}
+
+/* 调度策略 */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    Fcfs,                                      // 先来先服务
+    Sjf,                                       // 非抢占的短作业优先
+    Srtf,                                      // 抢占的短作业优先(最短剩余时间优先)
+    RoundRobin { quantum: u32 },               // 时间片轮转
+    Priority { preemptive: bool, aging: u32 }, // 每等待aging个时间单位优先级提高1，为0时不老化
+    Mlfq { quanta: Vec<u32>, boost: u32 }, // 各级队列的时间片；每boost个时间单位全部提升一次，为0时不提升
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Policy::Fcfs => write!(f, "FCFS"),
+            Policy::Sjf => write!(f, "SJF"),
+            Policy::Srtf => write!(f, "SRTF"),
+            Policy::RoundRobin { quantum } => write!(f, "RR(q={})", quantum),
+            Policy::Priority { preemptive, aging } => write!(
+                f,
+                "Priority({}{})",
+                if *preemptive { "抢占" } else { "非抢占" },
+                if *aging > 0 {
+                    format!(",aging={}", aging)
+                } else {
+                    String::new()
+                }
+            ),
+            Policy::Mlfq { quanta, boost } => write!(f, "MLFQ({:?},boost={})", quanta, boost),
+        }
+    }
+}
+
+/* 时间线上的一段：某个进程(None表示空闲)在[start, end)内占用CPU */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slice {
+    pub pid: Option<usize>, // 进程在进程表中的下标
+    pub start: u32,
+    pub end: u32,
+}
+
+/* 单个进程的指标 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessStats {
+    pub completion: u32, // 完成时刻
+    pub turnaround: u32, // 周转时间 = 完成时刻 - 到达时刻
+    pub waiting: u32,    // 等待时间 = 周转时间 - 运行时间
+    pub response: u32,   // 响应时间 = 第一次运行的时刻 - 到达时刻
+}
+
+/* 一次调度的结果 */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub policy: String,
+    pub names: Vec<String>,
+    pub timeline: Vec<Slice>,
+    pub stats: Vec<ProcessStats>, // 与进程表一一对应
+    pub context_switches: usize,  // 从一个进程切换到另一个进程的次数
+}
+
+impl Schedule {
+    fn average(&self, f: impl Fn(&ProcessStats) -> u32) -> f64 {
+        if self.stats.is_empty() {
+            return 0.0;
+        }
+        self.stats.iter().map(|s| f(s) as f64).sum::<f64>() / self.stats.len() as f64
+    }
+
+    // 平均周转时间
+    pub fn avg_turnaround(&self) -> f64 {
+        self.average(|s| s.turnaround)
+    }
+
+    // 平均等待时间
+    pub fn avg_waiting(&self) -> f64 {
+        self.average(|s| s.waiting)
+    }
+
+    // 平均响应时间
+    pub fn avg_response(&self) -> f64 {
+        self.average(|s| s.response)
+    }
+
+    // 所有进程完成的时刻
+    pub fn makespan(&self) -> u32 {
+        self.timeline.last().map_or(0, |s| s.end)
+    }
+
+    /* 文本甘特图，例如 | P1 0-3 | P2 3-5 | 空闲 5-6 | */
+    pub fn gantt(&self) -> String {
+        let mut chart = String::from("|");
+        for slice in &self.timeline {
+            let name = slice.pid.map_or("空闲", |pid| self.names[pid].as_str());
+            let _ = write!(chart, " {} {}-{} |", name, slice.start, slice.end);
+        }
+        chart
+    }
+
+    /* 每个进程的指标表 */
+    pub fn stats_table(&self) -> String {
+        let mut table = String::from("name,completion,turnaround,waiting,response\n");
+        for (name, s) in self.names.iter().zip(&self.stats) {
+            let _ = writeln!(
+                table,
+                "{},{},{},{},{}",
+                name, s.completion, s.turnaround, s.waiting, s.response
+            );
+        }
+        table
+    }
+}
+
+/* 多种策略的对比表 */
+pub fn comparison_table(schedules: &[Schedule]) -> String {
+    let mut table =
+        String::from("policy,avg_turnaround,avg_waiting,avg_response,context_switches,makespan\n");
+    for s in schedules {
+        let _ = writeln!(
+            table,
+            "{},{:.2},{:.2},{:.2},{},{}",
+            s.policy,
+            s.avg_turnaround(),
+            s.avg_waiting(),
+            s.avg_response(),
+            s.context_switches,
+            s.makespan()
+        );
+    }
+    table
+}
+
+/* 调度器在每个时间点能看到的信息 */
+struct Ctx<'a> {
+    now: u32,
+    procs: &'a [Process],
+    remaining: &'a [u32], // 每个进程的剩余运行时间
+}
+
+/* 就绪队列：不同策略的差别全部体现在这里 */
+trait ReadyQueue {
+    // 进程进入就绪队列；slice是它刚刚连续运行的时间，新到达的进程为0
+    fn push(&mut self, pid: usize, slice: u32, ctx: &Ctx);
+
+    // 选出下一个运行的进程
+    fn pop(&mut self, ctx: &Ctx) -> Option<usize>;
+
+    // 正在运行的进程是否应当被打断并放回就绪队列
+    fn should_preempt(&self, _pid: usize, _slice: u32, _ctx: &Ctx) -> bool {
+        false
+    }
+
+    // 每个时间点开始时调用；返回true表示正在运行的进程的时间片从头计算
+    fn tick(&mut self, _ctx: &Ctx) -> bool {
+        false
+    }
+}
+
+// 可以增长的先进先出队列
+fn fifo() -> ArrayQueue<usize> {
+    ArrayQueue::with_policy(8, OverflowPolicy::Grow)
+}
+
+/* 先来先服务 */
+struct Fcfs(ArrayQueue<usize>);
+
+impl ReadyQueue for Fcfs {
+    fn push(&mut self, pid: usize, _slice: u32, _ctx: &Ctx) {
+        let _ = self.0.push(pid);
+    }
+
+    fn pop(&mut self, _ctx: &Ctx) -> Option<usize> {
+        self.0.pop()
+    }
+}
+
+/* 时间片轮转 */
+struct RoundRobin {
+    queue: ArrayQueue<usize>,
+    quantum: u32,
+}
+
+impl ReadyQueue for RoundRobin {
+    fn push(&mut self, pid: usize, _slice: u32, _ctx: &Ctx) {
+        let _ = self.queue.push(pid);
+    }
+
+    fn pop(&mut self, _ctx: &Ctx) -> Option<usize> {
+        self.queue.pop()
+    }
+
+    fn should_preempt(&self, _pid: usize, slice: u32, _ctx: &Ctx) -> bool {
+        slice >= self.quantum
+    }
+}
+
+/* 短作业优先：按(剩余时间, 到达时刻, 下标)排序的小顶堆 */
+struct ShortestJob {
+    heap: BinaryHeap<(u32, u32, usize), MinComparator>,
+    preemptive: bool,
+}
+
+impl ReadyQueue for ShortestJob {
+    fn push(&mut self, pid: usize, _slice: u32, ctx: &Ctx) {
+        self.heap
+            .push((ctx.remaining[pid], ctx.procs[pid].arrival, pid));
+    }
+
+    fn pop(&mut self, _ctx: &Ctx) -> Option<usize> {
+        self.heap.pop().map(|(_, _, pid)| pid)
+    }
+
+    fn should_preempt(&self, pid: usize, _slice: u32, ctx: &Ctx) -> bool {
+        self.preemptive
+            && self
+                .heap
+                .peek()
+                .is_some_and(|&(remaining, _, _)| remaining < ctx.remaining[pid])
+    }
+}
+
+/* 带老化的优先级调度 */
+struct PriorityAging {
+    ready: Vec<(usize, u32)>, // (进程, 进入就绪队列的时刻)
+    preemptive: bool,
+    aging: u32,
+    running: u32, // 正在运行的进程被选中时的优先级
+}
+
+impl PriorityAging {
+    // 等待中的进程当前的优先级
+    fn effective(&self, pid: usize, since: u32, ctx: &Ctx) -> u32 {
+        // aging为0时不老化
+        let steps = (ctx.now - since).checked_div(self.aging).unwrap_or(0);
+        ctx.procs[pid].priority.saturating_sub(steps)
+    }
+
+    // 就绪队列中最优先的进程的位置；优先级相同时先到者优先
+    fn best(&self, ctx: &Ctx) -> Option<(usize, u32)> {
+        self.ready
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &(pid, since))| {
+                (self.effective(pid, since, ctx), ctx.procs[pid].arrival, pid)
+            })
+            .map(|(i, &(pid, since))| (i, self.effective(pid, since, ctx)))
+    }
+}
+
+impl ReadyQueue for PriorityAging {
+    fn push(&mut self, pid: usize, _slice: u32, ctx: &Ctx) {
+        self.ready.push((pid, ctx.now));
+    }
+
+    fn pop(&mut self, ctx: &Ctx) -> Option<usize> {
+        let (i, priority) = self.best(ctx)?;
+        self.running = priority;
+        Some(self.ready.swap_remove(i).0)
+    }
+
+    fn should_preempt(&self, _pid: usize, _slice: u32, ctx: &Ctx) -> bool {
+        self.preemptive && self.best(ctx).is_some_and(|(_, p)| p < self.running)
+    }
+}
+
+/* 多级反馈队列 */
+struct Mlfq {
+    levels: Vec<ArrayQueue<usize>>, // 下标越小优先级越高
+    quanta: Vec<u32>,
+    level: Vec<usize>, // 每个进程所在的级别
+    boost: u32,
+}
+
+impl ReadyQueue for Mlfq {
+    fn push(&mut self, pid: usize, slice: u32, _ctx: &Ctx) {
+        let level = &mut self.level[pid];
+        // 用完整个时间片说明是CPU密集型，降一级；被高级队列抢占的进程留在原级别
+        if slice >= self.quanta[*level] {
+            *level = (*level + 1).min(self.quanta.len() - 1);
+        }
+        let _ = self.levels[*level].push(pid);
+    }
+
+    fn pop(&mut self, _ctx: &Ctx) -> Option<usize> {
+        self.levels.iter_mut().find_map(|queue| queue.pop())
+    }
+
+    fn should_preempt(&self, pid: usize, slice: u32, _ctx: &Ctx) -> bool {
+        let level = self.level[pid];
+        slice >= self.quanta[level] || self.levels[..level].iter().any(|q| !q.is_empty())
+    }
+
+    fn tick(&mut self, ctx: &Ctx) -> bool {
+        if self.boost == 0 || ctx.now == 0 || !ctx.now.is_multiple_of(self.boost) {
+            return false;
+        }
+        // 所有进程(包括正在运行的)回到最高级；正在运行的进程的时间片也要重新开始，
+        // 否则它已经用掉的时间会立刻超过最高级的时间片，被打断后又降回下一级
+        for level in 1..self.levels.len() {
+            while let Some(pid) = self.levels[level].pop() {
+                let _ = self.levels[0].push(pid);
+            }
+        }
+        self.level.iter_mut().for_each(|level| *level = 0);
+        true
+    }
+}
+
+fn ready_queue(policy: &Policy, procs: usize) -> Box<dyn ReadyQueue> {
+    match policy {
+        Policy::Fcfs => Box::new(Fcfs(fifo())),
+        Policy::Sjf | Policy::Srtf => Box::new(ShortestJob {
+            heap: BinaryHeap::new_min(),
+            preemptive: *policy == Policy::Srtf,
+        }),
+        Policy::RoundRobin { quantum } => {
+            assert!(*quantum > 0, "时间片必须大于0");
+            Box::new(RoundRobin {
+                queue: fifo(),
+                quantum: *quantum,
+            })
+        }
+        Policy::Priority { preemptive, aging } => Box::new(PriorityAging {
+            ready: Vec::new(),
+            preemptive: *preemptive,
+            aging: *aging,
+            running: 0,
+        }),
+        Policy::Mlfq { quanta, boost } => {
+            assert!(
+                !quanta.is_empty() && quanta.iter().all(|&q| q > 0),
+                "至少需要一级队列，且时间片必须大于0"
+            );
+            Box::new(Mlfq {
+                levels: quanta.iter().map(|_| fifo()).collect(),
+                quanta: quanta.clone(),
+                level: vec![0; procs],
+                boost: *boost,
+            })
+        }
+    }
+}
+
+/* 按给定策略调度进程表 */
+pub fn schedule(procs: &[Process], policy: &Policy) -> Schedule {
+    let mut queue = ready_queue(policy, procs.len());
+    // 按到达时刻排序，到达时刻相同时保持进程表中的顺序
+    let mut order: Vec<usize> = (0..procs.len()).collect();
+    order.sort_by_key(|&pid| procs[pid].arrival);
+    let mut next_arrival = 0;
+
+    let mut remaining: Vec<u32> = procs.iter().map(|p| p.burst).collect();
+    let mut first_run: Vec<Option<u32>> = vec![None; procs.len()];
+    let mut completion = vec![0; procs.len()];
+    let mut timeline: Vec<Slice> = Vec::new();
+    let mut context_switches = 0;
+    let mut last_run: Option<usize> = None;
+    let mut current: Option<(usize, u32)> = None; // (正在运行的进程, 本次已连续运行的时间)
+    let mut finished = 0;
+    let mut now = 0;
+
+    while finished < procs.len() {
+        let restart = queue.tick(&Ctx {
+            now,
+            procs,
+            remaining: &remaining,
+        });
+        if let (true, Some((_, slice))) = (restart, &mut current) {
+            *slice = 0;
+        }
+        // 1.到达的进程进入就绪队列
+        while next_arrival < order.len() && procs[order[next_arrival]].arrival <= now {
+            let ctx = Ctx {
+                now,
+                procs,
+                remaining: &remaining,
+            };
+            queue.push(order[next_arrival], 0, &ctx);
+            next_arrival += 1;
+        }
+        let ctx = Ctx {
+            now,
+            procs,
+            remaining: &remaining,
+        };
+        // 2.判断是否打断正在运行的进程
+        if let Some((pid, slice)) = current {
+            if queue.should_preempt(pid, slice, &ctx) {
+                queue.push(pid, slice, &ctx);
+                current = None;
+            }
+        }
+        // 3.CPU空闲时选出下一个进程
+        if current.is_none() {
+            current = queue.pop(&ctx).map(|pid| (pid, 0));
+        }
+        let Some((pid, slice)) = current else {
+            // 没有就绪的进程，空闲到下一个进程到达
+            let until = procs[order[next_arrival]].arrival;
+            push_slice(&mut timeline, None, now, until);
+            now = until;
+            continue;
+        };
+        if last_run.is_some_and(|last| last != pid) {
+            context_switches += 1;
+        }
+        last_run = Some(pid);
+        first_run[pid].get_or_insert(now);
+        // 4.运行一个时间单位
+        push_slice(&mut timeline, Some(pid), now, now + 1);
+        remaining[pid] -= 1;
+        now += 1;
+        current = Some((pid, slice + 1));
+        if remaining[pid] == 0 {
+            completion[pid] = now;
+            finished += 1;
+            current = None;
+        }
+    }
+
+    let stats = procs
+        .iter()
+        .enumerate()
+        .map(|(pid, p)| {
+            let turnaround = completion[pid] - p.arrival;
+            ProcessStats {
+                completion: completion[pid],
+                turnaround,
+                waiting: turnaround - p.burst,
+                response: first_run[pid].unwrap_or(p.arrival) - p.arrival,
+            }
+        })
+        .collect();
+    Schedule {
+        policy: policy.to_string(),
+        names: procs.iter().map(|p| p.name.clone()).collect(),
+        timeline,
+        stats,
+        context_switches,
+    }
+}
+
+// 追加一段时间线，与前一段属于同一进程且相邻时合并
+fn push_slice(timeline: &mut Vec<Slice>, pid: Option<usize>, start: u32, end: u32) {
+    if let Some(last) = timeline.last_mut() {
+        if last.pid == pid && last.end == start {
+            last.end = end;
+            return;
+        }
+    }
+    timeline.push(Slice { pid, start, end });
+}
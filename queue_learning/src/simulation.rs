@@ -0,0 +1,385 @@
+/* 离散事件模拟
+    淘宝订单处理、打印机任务队列都可以抽象为“顾客到达 -> 排队 -> 服务台服务 -> 离开”。
+    系统状态只在到达、服务结束等离散的时刻发生变化，因此不必按固定步长推进时钟，而是维护一个按时间排序的事件日历：
+        1.从日历(小顶堆)中取出最早的事件，把时钟直接跳到该事件的时刻；
+        2.处理事件，更新系统状态，并可能向日历中加入新的事件(下一位顾客的到达、某个服务台的服务结束)；
+        3.重复直到日历为空。
+    排队规则决定服务台空闲时下一个服务谁：
+        1.先进先出(FIFO)：按到达顺序服务，用ArrayQueue实现。
+        2.优先级：优先级数值小的先服务，同一优先级内先到先服务，用二叉堆实现，不抢占正在服务的顾客。
+        3.时间片轮转：每次最多服务一个时间片，没服务完就回到队尾，像打印机轮流打印每个任务的几页。 */
+
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use heap::binary_heap::{BinaryHeap, MinComparator};
+
+use crate::array_queue::{ArrayQueue, OverflowPolicy};
+use crate::rng::{Distribution, Rng};
+
+/* 排队规则 */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Discipline {
+    Fifo,                        // 先进先出
+    Priority,                    // 非抢占的优先级调度
+    RoundRobin { quantum: f64 }, // 时间片轮转
+}
+
+/* 模拟参数 */
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub servers: usize,           // 服务台数量
+    pub arrival: Distribution,    // 相邻两位顾客的到达间隔
+    pub service: Distribution,    // 服务时间
+    pub priority_levels: u32,     // 顾客的优先级均匀分布在[0, priority_levels)中，0最高
+    pub discipline: Discipline,   // 排队规则
+    pub horizon: f64,             // 只在[0, horizon)内产生到达，之后把已到达的顾客服务完
+    pub sample_interval: f64,     // 记录队列长度的时间间隔
+    pub seed: u64,                // 随机数种子
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            servers: 1,
+            arrival: Distribution::Exponential(1.0),
+            service: Distribution::Exponential(0.8),
+            priority_levels: 1,
+            discipline: Discipline::Fifo,
+            horizon: 1000.0,
+            sample_interval: 10.0,
+            seed: 1,
+        }
+    }
+}
+
+/* 某一时刻的系统快照 */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub time: f64,
+    pub queue_len: usize,    // 排队(不含正在服务)的顾客数
+    pub busy_servers: usize, // 忙碌的服务台数
+}
+
+/* 模拟结果 */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimReport {
+    pub served: usize,                 // 服务完成的顾客数
+    pub avg_wait: f64,                 // 平均等待时间(逗留时间减去服务时间)
+    pub max_wait: f64,                 // 最长等待时间
+    pub avg_wait_by_priority: Vec<f64>, // 各优先级的平均等待时间
+    pub avg_queue_len: f64,            // 按时间加权的平均队列长度
+    pub max_queue_len: usize,          // 最长队列长度
+    pub utilization: f64,              // 所有服务台的平均利用率
+    pub server_utilization: Vec<f64>,  // 每个服务台的利用率
+    pub end_time: f64,                 // 最后一位顾客离开的时刻
+    pub samples: Vec<Sample>,          // 队列长度随时间的变化
+}
+
+impl SimReport {
+    /* 队列长度随时间变化的CSV */
+    pub fn timeline_csv(&self) -> String {
+        let mut csv = String::from("time,queue_len,busy_servers\n");
+        for s in &self.samples {
+            let _ = writeln!(csv, "{:.3},{},{}", s.time, s.queue_len, s.busy_servers);
+        }
+        csv
+    }
+}
+
+/* 多次模拟的汇总CSV，每次模拟一行 */
+pub fn summary_csv(reports: &[(&str, &SimReport)]) -> String {
+    let mut csv =
+        String::from("scenario,served,avg_wait,max_wait,avg_queue_len,max_queue_len,utilization\n");
+    for (name, r) in reports {
+        let _ = writeln!(
+            csv,
+            "{},{},{:.4},{:.4},{:.4},{},{:.4}",
+            name, r.served, r.avg_wait, r.max_wait, r.avg_queue_len, r.max_queue_len, r.utilization
+        );
+    }
+    csv
+}
+
+/* 顾客 */
+#[derive(Debug, Clone)]
+struct Customer {
+    id: usize,
+    arrival: f64,   // 到达时刻
+    priority: u32,  // 优先级，0最高
+    service: f64,   // 总服务时间
+    remaining: f64, // 剩余服务时间
+}
+
+/* 在优先级队列中的排序：优先级数值小的在前，同一优先级按到达顺序 */
+struct Ranked(Customer);
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.priority, self.0.id).cmp(&(other.0.priority, other.0.id))
+    }
+}
+
+/* 等待队列 */
+enum Line {
+    Fifo(ArrayQueue<Customer>),
+    Priority(BinaryHeap<Ranked, MinComparator>),
+}
+
+impl Line {
+    fn size(&self) -> usize {
+        match self {
+            Line::Fifo(queue) => queue.size(),
+            Line::Priority(heap) => heap.size(),
+        }
+    }
+
+    fn push(&mut self, customer: Customer) {
+        match self {
+            // Grow策略的队列不会拒绝入队
+            Line::Fifo(queue) => {
+                let _ = queue.push(customer);
+            }
+            Line::Priority(heap) => heap.push(Ranked(customer)),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Customer> {
+        match self {
+            Line::Fifo(queue) => queue.pop(),
+            Line::Priority(heap) => heap.pop().map(|r| r.0),
+        }
+    }
+}
+
+/* 事件类型 */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+    Arrival,       // 一位顾客到达
+    Finish(usize), // 某个服务台完成一次服务(或一个时间片)
+    Sample,        // 记录一次系统快照
+}
+
+/* 日历中的事件：按时刻排序，时刻相同时按加入日历的顺序 */
+struct Event {
+    time: f64,
+    seq: u64,
+    kind: EventKind,
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.time
+            .total_cmp(&other.time)
+            .then(self.seq.cmp(&other.seq))
+    }
+}
+
+/* 模拟器的运行状态 */
+struct Simulation<'a> {
+    config: &'a SimConfig,
+    rng: Rng,
+    calendar: BinaryHeap<Event, MinComparator>, // 事件日历
+    seq: u64,
+    now: f64,
+    next_id: usize,
+    line: Line,
+    servers: Vec<Option<Customer>>, // 每个服务台正在服务的顾客
+    busy: Vec<f64>,                 // 每个服务台的累计忙碌时间
+    queue_area: f64,                // 队列长度对时间的积分
+    max_queue_len: usize,
+    waits: Vec<(u32, f64)>,         // 每位离开的顾客的(优先级, 等待时间)
+    samples: Vec<Sample>,
+}
+
+impl Simulation<'_> {
+    // 向日历中加入一个事件
+    fn schedule(&mut self, time: f64, kind: EventKind) {
+        self.seq += 1;
+        self.calendar.push(Event {
+            time,
+            seq: self.seq,
+            kind,
+        });
+    }
+
+    // 安排下一位顾客的到达
+    fn schedule_arrival(&mut self) {
+        let time = self.now + self.config.arrival.sample(&mut self.rng);
+        if time < self.config.horizon {
+            self.schedule(time, EventKind::Arrival);
+        }
+    }
+
+    // 让空闲的服务台从队列中取出顾客开始服务
+    fn dispatch(&mut self) {
+        for server in 0..self.servers.len() {
+            if self.servers[server].is_some() {
+                continue;
+            }
+            let Some(customer) = self.line.pop() else {
+                return;
+            };
+            let slice = match self.config.discipline {
+                Discipline::RoundRobin { quantum } => customer.remaining.min(quantum),
+                _ => customer.remaining,
+            };
+            self.busy[server] += slice;
+            self.servers[server] = Some(customer);
+            self.schedule(self.now + slice, EventKind::Finish(server));
+        }
+    }
+
+    fn on_arrival(&mut self) {
+        let service = self.config.service.sample(&mut self.rng);
+        let priority = self.rng.gen_range(0..self.config.priority_levels.max(1) as u64) as u32;
+        self.line.push(Customer {
+            id: self.next_id,
+            arrival: self.now,
+            priority,
+            service,
+            remaining: service,
+        });
+        self.next_id += 1;
+        self.max_queue_len = self.max_queue_len.max(self.line.size());
+        self.schedule_arrival();
+    }
+
+    fn on_finish(&mut self, server: usize) {
+        let mut customer = self.servers[server].take().expect("服务结束时服务台上有顾客");
+        customer.remaining = match self.config.discipline {
+            Discipline::RoundRobin { quantum } => customer.remaining - quantum,
+            _ => 0.0,
+        };
+        if customer.remaining > 1e-9 {
+            // 时间片用完但还没服务完，回到队尾
+            self.line.push(customer);
+        } else {
+            let wait = self.now - customer.arrival - customer.service;
+            self.waits.push((customer.priority, wait.max(0.0)));
+        }
+    }
+
+    fn on_sample(&mut self) {
+        self.samples.push(Sample {
+            time: self.now,
+            queue_len: self.line.size(),
+            busy_servers: self.servers.iter().filter(|s| s.is_some()).count(),
+        });
+        // 还有其他事件时才继续采样，否则模拟已经结束
+        if !self.calendar.is_empty() {
+            self.schedule(self.now + self.config.sample_interval, EventKind::Sample);
+        }
+    }
+
+    fn run(mut self) -> SimReport {
+        self.schedule_arrival();
+        self.schedule(0.0, EventKind::Sample);
+        let mut end_time = 0.0;
+        while let Some(event) = self.calendar.pop() {
+            // 上一个事件到这个事件之间队列长度不变
+            self.queue_area += self.line.size() as f64 * (event.time - self.now);
+            self.now = event.time;
+            match event.kind {
+                EventKind::Arrival => self.on_arrival(),
+                EventKind::Finish(server) => {
+                    self.on_finish(server);
+                    end_time = self.now;
+                }
+                EventKind::Sample => self.on_sample(),
+            }
+            self.dispatch();
+        }
+        self.report(end_time)
+    }
+
+    fn report(self, end_time: f64) -> SimReport {
+        let served = self.waits.len();
+        let mean = |waits: &mut dyn Iterator<Item = f64>| {
+            let (sum, n) = waits.fold((0.0, 0usize), |(s, n), w| (s + w, n + 1));
+            if n == 0 {
+                0.0
+            } else {
+                sum / n as f64
+            }
+        };
+        let levels = self.config.priority_levels.max(1);
+        let avg_wait_by_priority = (0..levels)
+            .map(|p| mean(&mut self.waits.iter().filter(|w| w.0 == p).map(|w| w.1)))
+            .collect();
+        let per_server = |busy: f64| if end_time > 0.0 { busy / end_time } else { 0.0 };
+        SimReport {
+            served,
+            avg_wait: mean(&mut self.waits.iter().map(|w| w.1)),
+            max_wait: self.waits.iter().map(|w| w.1).fold(0.0, f64::max),
+            avg_wait_by_priority,
+            avg_queue_len: if end_time > 0.0 {
+                self.queue_area / end_time
+            } else {
+                0.0
+            },
+            max_queue_len: self.max_queue_len,
+            utilization: per_server(self.busy.iter().sum::<f64>()) / self.busy.len() as f64,
+            server_utilization: self.busy.iter().map(|&b| per_server(b)).collect(),
+            end_time,
+            samples: self.samples,
+        }
+    }
+}
+
+/* 按给定参数运行一次模拟 */
+pub fn simulate(config: &SimConfig) -> SimReport {
+    assert!(config.servers > 0, "至少需要一个服务台");
+    assert!(config.sample_interval > 0.0, "采样间隔必须大于0");
+    if let Discipline::RoundRobin { quantum } = config.discipline {
+        assert!(quantum > 0.0, "时间片必须大于0");
+    }
+    let line = match config.discipline {
+        Discipline::Priority => Line::Priority(BinaryHeap::new_min()),
+        _ => Line::Fifo(ArrayQueue::with_policy(16, OverflowPolicy::Grow)),
+    };
+    Simulation {
+        config,
+        rng: Rng::new(config.seed),
+        calendar: BinaryHeap::new_min(),
+        seq: 0,
+        now: 0.0,
+        next_id: 0,
+        line,
+        servers: vec![None; config.servers],
+        busy: vec![0.0; config.servers],
+        queue_area: 0.0,
+        max_queue_len: 0,
+        waits: Vec::new(),
+        samples: Vec::new(),
+    }
+    .run()
+}
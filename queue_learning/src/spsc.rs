@@ -0,0 +1,203 @@
+/* 无锁单生产者单消费者环形队列
+    只有一个线程入队、一个线程出队时，不需要互斥锁：生产者只写tail，消费者只写head，
+    双方通过Acquire/Release读取对方的下标即可保证看到完整写入的元素。
+    head和tail都是单调递增的计数器，tail - head即为队列长度，元素位置仍按ArrayQueue的环形下标计算。
+    head和tail分别填充到独立的缓存行，避免两个线程反复争夺同一缓存行(伪共享)。 */
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::array_queue::wrap_index;
+
+/* 按缓存行对齐的包装类型，保证被包装的值独占一条缓存行 */
+#[repr(align(64))]
+pub struct CachePadded<T>(pub T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/* 生产者和消费者共享的环形数组，通过split()拆分为生产者和消费者两个句柄 */
+pub struct SpscRing<T> {
+    head: CachePadded<AtomicUsize>, // 下一个要出队的位置，只由消费者修改
+    tail: CachePadded<AtomicUsize>, // 下一个要入队的位置，只由生产者修改
+    elems: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+// 每个槽位同一时刻只会被生产者或消费者之一访问
+unsafe impl<T: Send> Sync for SpscRing<T> {}
+
+impl<T> SpscRing<T> {
+    // 构造函数，capacity必须大于0
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "环形队列的容量必须大于0");
+        Self {
+            head: CachePadded(AtomicUsize::new(0)),
+            tail: CachePadded(AtomicUsize::new(0)),
+            elems: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+        }
+    }
+
+    /* 拆分为生产者和消费者，二者可以分别移动到不同的线程 */
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        let ring = Arc::new(self);
+        (
+            Producer {
+                ring: Arc::clone(&ring),
+                tail: 0,
+                cached_head: 0,
+            },
+            Consumer {
+                ring,
+                head: 0,
+                cached_tail: 0,
+            },
+        )
+    }
+
+    // 获取队列容量
+    pub fn capacity(&self) -> usize {
+        self.elems.len()
+    }
+
+    fn slot(&self, counter: usize) -> *mut MaybeUninit<T> {
+        self.elems[wrap_index(0, counter, self.capacity())].get()
+    }
+}
+
+impl<T> Drop for SpscRing<T> {
+    fn drop(&mut self) {
+        // 释放尚未被消费的元素
+        let head = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        for counter in head..tail {
+            // SAFETY: [head, tail)内的槽位都已初始化，且此时没有其他线程访问
+            unsafe { (*self.slot(counter)).assume_init_drop() };
+        }
+    }
+}
+
+/* 生产者句柄 */
+pub struct Producer<T> {
+    ring: Arc<SpscRing<T>>,
+    tail: usize,        // 本地的tail，与共享的tail一致
+    cached_head: usize, // 最近一次读到的head，只有看起来已满时才重新读取
+}
+
+impl<T> Producer<T> {
+    // 获取队列容量
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    // 当前可写入的空位数量
+    fn free_slots(&mut self) -> usize {
+        let cap = self.capacity();
+        if self.tail - self.cached_head == cap {
+            self.cached_head = self.ring.head.load(Ordering::Acquire);
+        }
+        cap - (self.tail - self.cached_head)
+    }
+
+    /* 入队，队列已满时通过Err原样返回元素 */
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        if self.free_slots() == 0 {
+            return Err(elem);
+        }
+        // SAFETY: tail处的槽位为空，消费者在tail发布之前不会访问它
+        unsafe { (*self.ring.slot(self.tail)).write(elem) };
+        self.tail += 1;
+        self.ring.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+
+    /* 批量入队：尽可能多地从iter中取出元素写入队列，只发布一次tail，返回写入的数量 */
+    pub fn push_batch<I: Iterator<Item = T>>(&mut self, iter: &mut I) -> usize {
+        let free = self.free_slots();
+        let mut count = 0;
+        for elem in iter.take(free) {
+            // SAFETY: 同push，且写入的槽位都在空闲区间内
+            unsafe { (*self.ring.slot(self.tail + count)).write(elem) };
+            count += 1;
+        }
+        if count > 0 {
+            self.tail += count;
+            self.ring.tail.store(self.tail, Ordering::Release);
+        }
+        count
+    }
+
+    // 消费者是否已经被丢弃
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.ring) == 1
+    }
+}
+
+/* 消费者句柄 */
+pub struct Consumer<T> {
+    ring: Arc<SpscRing<T>>,
+    head: usize,        // 本地的head，与共享的head一致
+    cached_tail: usize, // 最近一次读到的tail，只有看起来为空时才重新读取
+}
+
+impl<T> Consumer<T> {
+    // 获取队列容量
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    // 当前可读取的元素数量
+    fn available(&mut self) -> usize {
+        if self.cached_tail == self.head {
+            self.cached_tail = self.ring.tail.load(Ordering::Acquire);
+        }
+        self.cached_tail - self.head
+    }
+
+    // 当前队列长度
+    pub fn size(&mut self) -> usize {
+        self.cached_tail = self.ring.tail.load(Ordering::Acquire);
+        self.cached_tail - self.head
+    }
+
+    /* 出队，队列为空时返回None */
+    pub fn pop(&mut self) -> Option<T> {
+        if self.available() == 0 {
+            return None;
+        }
+        // SAFETY: head < tail，槽位已由生产者写入并通过Release发布
+        let elem = unsafe { (*self.ring.slot(self.head)).assume_init_read() };
+        self.head += 1;
+        self.ring.head.store(self.head, Ordering::Release);
+        Some(elem)
+    }
+
+    /* 批量出队：最多取出max个元素追加到out，只发布一次head，返回取出的数量 */
+    pub fn pop_batch(&mut self, out: &mut Vec<T>, max: usize) -> usize {
+        let count = self.available().min(max);
+        out.reserve(count);
+        for i in 0..count {
+            // SAFETY: 同pop，[head, head + count)内的槽位都已发布
+            out.push(unsafe { (*self.ring.slot(self.head + i)).assume_init_read() });
+        }
+        if count > 0 {
+            self.head += count;
+            self.ring.head.store(self.head, Ordering::Release);
+        }
+        count
+    }
+
+    // 生产者是否已经被丢弃；此时队列中剩余的元素仍然可以取出
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.ring) == 1
+    }
+}
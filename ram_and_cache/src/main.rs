@@ -24,7 +24,305 @@
         1.在做算法题的时候，我们通常会倾向于选择基于数组的栈，因为它提供了更高的操作效率和随机访问的能力，代价仅仅是需要预先为数组分配一定的存储空间。
         2.如果数据量非常大、动态性很高、栈的预估大小很难估计，那么基于链表实现的栈更合适。链表能够将大量数据分散存储在内存的不同部分，并且避免了数组扩容时带来的额外开销。*/
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use hash_collision::chaining::HashMapChaining;
+use hash_collision::open_addressing::OpenAddressingMap;
+use hash_collision::probe::{DoubleHashing, LinearProbing, ProbeStrategy, QuadraticProbing};
+use hash_collision::swiss::SwissMap;
+use heap::indexed_heap::IndexedHeap;
+use queue_learning::blocking_queue::BlockingQueue;
+use queue_learning::rng::Rng;
+use queue_learning::spsc::SpscRing;
 
 fn main() {
     println!("内存与缓存，学习！");
+
+    bench_queues();
+    bench_heap_arity();
+    bench_probe_lengths();
+    bench_hash_maps();
+}
+
+/* 队列吞吐量对比：一个生产者线程向一个消费者线程发送COUNT个u64。
+    SpscRing的head和tail各占一条缓存行，且没有锁；BlockingQueue每次操作都要加锁并可能唤醒对方线程；
+    mpsc::sync_channel是标准库的有界通道。队列满或空时让出CPU而不是空转，这样在单核机器上也能正常运行。
+    建议使用 cargo run --release 运行。 */
+const COUNT: u64 = 1_000_000;
+const CAPACITY: usize = 1024;
+
+fn bench_queues() {
+    report("SpscRing", bench_spsc());
+    report("SpscRing(批量)", bench_spsc_batch());
+    report("BlockingQueue", bench_blocking_queue());
+    report("mpsc::sync_channel", bench_sync_channel());
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let mops = COUNT as f64 / elapsed.as_secs_f64() / 1e6;
+    println!("{:<20} {:>10.2?} {:>8.2} Mops/s", name, elapsed, mops);
+}
+
+fn bench_spsc() -> Duration {
+    let (mut producer, mut consumer) = SpscRing::new(CAPACITY).split();
+    let start = Instant::now();
+    let handle = thread::spawn(move || {
+        for i in 0..COUNT {
+            let mut elem = i;
+            while let Err(back) = producer.push(elem) {
+                elem = back;
+                thread::yield_now();
+            }
+        }
+    });
+    let mut sum = 0;
+    let mut received = 0;
+    while received < COUNT {
+        match consumer.pop() {
+            Some(elem) => {
+                sum += elem;
+                received += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    handle.join().unwrap();
+    assert_eq!(sum, COUNT * (COUNT - 1) / 2);
+    start.elapsed()
+}
+
+fn bench_spsc_batch() -> Duration {
+    let (mut producer, mut consumer) = SpscRing::new(CAPACITY).split();
+    let start = Instant::now();
+    let handle = thread::spawn(move || {
+        let mut elems = 0..COUNT;
+        while elems.start < COUNT {
+            if producer.push_batch(&mut elems) == 0 {
+                thread::yield_now();
+            }
+        }
+    });
+    let mut sum = 0;
+    let mut received = 0;
+    let mut batch = Vec::with_capacity(CAPACITY);
+    while received < COUNT {
+        let n = consumer.pop_batch(&mut batch, CAPACITY);
+        if n == 0 {
+            thread::yield_now();
+        }
+        received += n as u64;
+        sum += batch.drain(..).sum::<u64>();
+    }
+    handle.join().unwrap();
+    assert_eq!(sum, COUNT * (COUNT - 1) / 2);
+    start.elapsed()
+}
+
+fn bench_blocking_queue() -> Duration {
+    let queue = Arc::new(BlockingQueue::new(CAPACITY));
+    let start = Instant::now();
+    let handle = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            for i in 0..COUNT {
+                queue.push(i).unwrap();
+            }
+            queue.close();
+        })
+    };
+    let mut sum = 0;
+    while let Some(elem) = queue.pop() {
+        sum += elem;
+    }
+    handle.join().unwrap();
+    assert_eq!(sum, COUNT * (COUNT - 1) / 2);
+    start.elapsed()
+}
+
+fn bench_sync_channel() -> Duration {
+    let (sender, receiver) = mpsc::sync_channel(CAPACITY);
+    let start = Instant::now();
+    let handle = thread::spawn(move || {
+        for i in 0..COUNT {
+            sender.send(i).unwrap();
+        }
+    });
+    let sum: u64 = receiver.iter().sum();
+    handle.join().unwrap();
+    assert_eq!(sum, COUNT * (COUNT - 1) / 2);
+    start.elapsed()
+}
+
+/* d叉堆的分支数对性能的影响：先插入N个键，再对每个键执行一次decrease_key，最后全部出堆。
+    分支数越大，树越矮，上浮越快；下沉时每层要比较d个子节点，但它们在数组中连续存放，同一条缓存行就能装下。
+    堆很小时全部数据都在缓存中，差异主要来自比较次数；堆超出缓存后，层数减少带来的缓存未命中减少更加明显。 */
+fn bench_heap_arity() {
+    for n in [1 << 12, 1 << 16] {
+        for arity in [2, 4, 8] {
+            let elapsed = bench_indexed_heap(n, arity);
+            let mops = (3 * n) as f64 / elapsed.as_secs_f64() / 1e6;
+            println!(
+                "IndexedHeap(n={:<7} d={}) {:>10.2?} {:>8.2} Mops/s",
+                n, arity, elapsed, mops
+            );
+        }
+    }
+}
+
+fn bench_indexed_heap(n: usize, arity: usize) -> Duration {
+    // 线性同余生成伪随机优先级，保证每次运行的输入相同
+    let mut seed = 12345u64;
+    let priorities: Vec<u64> = (0..n)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) + n as u64
+        })
+        .collect();
+    let start = Instant::now();
+    let mut heap: IndexedHeap<usize, u64> = IndexedHeap::with_arity(arity);
+    for (key, &p) in priorities.iter().enumerate() {
+        heap.push(key, p);
+    }
+    for (key, &p) in priorities.iter().enumerate() {
+        heap.decrease_key(&key, p - (p % n as u64));
+    }
+    let mut last = 0;
+    while let Some((_, p)) = heap.pop() {
+        assert!(p >= last);
+        last = p;
+    }
+    start.elapsed()
+}
+
+/* 开放寻址的探测长度：容量固定为2^16，逐个插入随机key，在负载因子达到α时统计
+    命中查询(key存在)的平均与最大探测次数，以及未命中查询(key不存在)的平均探测次数。
+    线性探测的理论期望为：命中 (1 + 1/(1-α)) / 2，未命中 (1 + 1/(1-α)²) / 2。
+    线性探测的探测路径连续，缓存友好，但负载高时聚集严重；平方探测与双重哈希的路径更分散，探测次数更少，每次探测却更可能缓存未命中。 */
+const PROBE_CAPACITY: usize = 1 << 16;
+const LOAD_FACTORS: [f64; 5] = [0.5, 0.7, 0.8, 0.9, 0.95];
+
+fn bench_probe_lengths() {
+    println!(
+        "{:<18} {:>5} {:>10} {:>8} {:>10} {:>10}",
+        "探测策略", "α", "命中平均", "命中最大", "未命中平均", "耗时"
+    );
+    for &alpha in &LOAD_FACTORS {
+        let hit = (1.0 + 1.0 / (1.0 - alpha)) / 2.0;
+        let miss = (1.0 + 1.0 / ((1.0 - alpha) * (1.0 - alpha))) / 2.0;
+        println!(
+            "{:<18} {:>5.2} {:>10.2} {:>8} {:>10.2}",
+            "线性(理论)", alpha, hit, "-", miss
+        );
+    }
+    report_probe_lengths("线性探测", LinearProbing);
+    report_probe_lengths("平方探测", QuadraticProbing);
+    report_probe_lengths("双重哈希", DoubleHashing);
+}
+
+fn report_probe_lengths<P: ProbeStrategy>(name: &str, strategy: P) {
+    let hasher = BuildHasherDefault::<DefaultHasher>::default();
+    // 阈值设为0.99，插入过程中容量很快增长到PROBE_CAPACITY，之后不再扩容
+    let mut map =
+        OpenAddressingMap::with_hasher_and_strategy(hasher, strategy).with_load_thres(0.99);
+    let mut rng = Rng::new(2024);
+    let mut keys = Vec::new();
+    for &alpha in &LOAD_FACTORS {
+        while keys.len() < (alpha * PROBE_CAPACITY as f64) as usize {
+            let key = rng.next_u64() >> 1; // 非负数，下方用负数作为一定不存在的key
+            map.insert(key as i64, ());
+            keys.push(key as i64);
+        }
+        assert_eq!(map.capacity(), PROBE_CAPACITY);
+        let start = Instant::now();
+        let hits: Vec<usize> = keys.iter().map(|key| map.probe_count(key)).collect();
+        let misses: usize = (1..=10_000i64).map(|key| map.probe_count(&-key)).sum();
+        let elapsed = start.elapsed();
+        println!(
+            "{:<18} {:>5.2} {:>10.2} {:>8} {:>10.2} {:>10.2?}",
+            name,
+            alpha,
+            hits.iter().sum::<usize>() as f64 / hits.len() as f64,
+            hits.iter().max().unwrap(),
+            misses as f64 / 10_000.0,
+            elapsed
+        );
+    }
+}
+
+/* 各个哈希表没有共同的trait，但insert/get/remove的签名相同，用宏生成相同的测试代码 */
+macro_rules! bench_map {
+    ($name:expr, $n:expr, $map:expr, $present:expr, $absent:expr) => {{
+        let mops = |elapsed: Duration| $n as f64 / elapsed.as_secs_f64() / 1e6;
+        let mut map = $map;
+        let start = Instant::now();
+        for &key in $present {
+            map.insert(key, key);
+        }
+        let insert = mops(start.elapsed());
+        let start = Instant::now();
+        for key in $present {
+            assert_eq!(map.get(key), Some(key));
+        }
+        let hit = mops(start.elapsed());
+        let start = Instant::now();
+        for key in $absent {
+            assert_eq!(map.get(key), None);
+        }
+        let miss = mops(start.elapsed());
+        let start = Instant::now();
+        for key in $present {
+            assert_eq!(map.remove(key), Some(*key));
+        }
+        let remove = mops(start.elapsed());
+        println!(
+            "{:<20} n={:<7} {:>8.2} {:>8.2} {:>8.2} {:>8.2}",
+            $name, $n, insert, hit, miss, remove
+        );
+    }};
+}
+
+/* 哈希表的吞吐量对比：依次插入N个随机key，查询N个存在的key，查询N个不存在的key，再删除全部key，分别统计每种操作的速度。
+    四种哈希表使用相同的哈希算法(标准库的SipHash)，差异只来自内存布局与探测方式：
+        1.HashMapChaining：每个桶是一个独立分配的Vec，查询要先读桶再跳到Vec所在的内存，至少两次缓存未命中。
+        2.OpenAddressingMap：线性探测，键值对连续存放，但每探测一个桶都要比较一次key。
+        3.SwissMap：先在16个控制字节中用SWAR一次筛选出h2匹配的桶，几乎只在真正命中时才比较key；未命中的查询通常只读一组控制字节。
+        4.标准库的HashMap：同样是SwissTable，使用SIMD指令并且没有Option的额外开销，可以作为上限参考。
+    N超过缓存容量后，每次查询的缓存未命中次数成为主要开销。 */
+fn bench_hash_maps() {
+    println!(
+        "{:<28} {:>8} {:>8} {:>8} {:>8} (Mops/s)",
+        "哈希表", "插入", "命中", "未命中", "删除"
+    );
+    for n in [1 << 12, 1 << 16, 1 << 20] {
+        // 固定种子生成伪随机key，前N个用于插入，后N个一定不存在(最高位不同)
+        let mut rng = Rng::new(777);
+        let keys: Vec<u64> = (0..2 * n)
+            .map(|i| (rng.next_u64() >> 1) | ((i >= n) as u64) << 63)
+            .collect();
+        let (present, absent) = keys.split_at(n);
+        bench_map!(
+            "HashMapChaining",
+            n,
+            HashMapChaining::new(),
+            present,
+            absent
+        );
+        bench_map!(
+            "OpenAddressingMap",
+            n,
+            OpenAddressingMap::new(),
+            present,
+            absent
+        );
+        bench_map!("SwissMap", n, SwissMap::new(), present, absent);
+        bench_map!("std::HashMap", n, HashMap::new(), present, absent);
+    }
 }